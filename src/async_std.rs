@@ -0,0 +1,57 @@
+//! Temporary files and directories for the [`async-std`](https://crates.io/crates/async-std)
+//! runtime.
+//!
+//! The [`TempFile`] and [`TempDir`] types of this module mirror the core API of their tokio
+//! counterparts at the crate root, but perform their I/O via [`async_std::fs`] and implement
+//! the [`Read`](async_std::io::Read), [`Write`](async_std::io::Write) and
+//! [`Seek`](async_std::io::Seek) traits of `async-std`. They share [`Ownership`](crate::Ownership),
+//! [`Error`](crate::Error) and the naming of generated files and directories with the
+//! tokio types.
+//!
+//! The extended functionality of the tokio types, such as builders, persisting or
+//! positional I/O, is not available for these types.
+//!
+//! When dropped within a task, the file or directory is removed on the blocking thread pool of
+//! `async-std` rather than on the executor, and may therefore briefly outlive the instance.
+//! Use [`TempFile::close`] or [`TempDir::close`] to wait for the removal.
+//!
+//! ```
+//! use async_std::prelude::*;
+//! use async_tempfile::async_std::TempFile;
+//! use std::io::SeekFrom;
+//!
+//! # async_std::task::block_on(async {
+//! let mut file = TempFile::new().await?;
+//! file.write_all(b"data").await?;
+//! file.flush().await?;
+//! file.seek(SeekFrom::Start(0)).await?;
+//!
+//! let mut content = String::new();
+//! file.read_to_string(&mut content).await?;
+//! assert_eq!(content, "data");
+//!
+//! let path = file.file_path().clone();
+//! file.close().await?;
+//! assert!(!path.exists());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # }).unwrap();
+//! ```
+
+mod tempdir;
+mod tempfile;
+
+pub use tempdir::TempDir;
+pub use tempfile::TempFile;
+
+/// Runs the removal of a file or directory from `Drop`.
+///
+/// Within a task, the removal is moved to the blocking thread pool so that the executor is not
+/// stalled. Elsewhere, it runs on the calling thread.
+fn remove_off_executor(removal: impl FnOnce() + Send + 'static) {
+    if ::async_std::task::try_current().is_some() {
+        // Dropping the handle detaches the task rather than cancelling it.
+        drop(::async_std::task::spawn_blocking(removal));
+    } else {
+        removal();
+    }
+}
@@ -0,0 +1,229 @@
+use super::remove_off_executor;
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS, DIR_PREFIX};
+use crate::creation;
+use crate::errors::check_directory_metadata;
+use crate::removal;
+use crate::trace;
+use crate::{Error, Ownership};
+use ::async_std::task;
+use std::borrow::Borrow;
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A named temporary directory that will be cleaned automatically
+/// after the last reference to it is dropped, using `async-std` for I/O.
+pub struct TempDir {
+    /// A shared pointer to the owned (or non-owned) directory.
+    /// The `Arc` ensures that the enclosed dir is kept alive
+    /// until all references to it are dropped.
+    core: Arc<TempDirCore>,
+}
+
+/// The instance that tracks the temporary directory.
+/// If dropped, the directory will be deleted.
+struct TempDirCore {
+    /// The path of the contained directory.
+    path: PathBuf,
+
+    /// Whether the directory is deleted on drop.
+    ownership: Ownership,
+}
+
+impl TempDir {
+    /// Creates a new temporary directory in the default location.
+    /// When the instance goes out of scope, the directory will be deleted.
+    ///
+    /// The default location is resolved via [`default_dir`](crate::default_dir) on every call.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::async_std::TempDir;
+    /// # async_std::task::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// let dir_path = dir.dir_path().clone();
+    /// assert!(dir_path.is_dir());
+    ///
+    /// dir.close().await?;
+    /// assert!(!dir_path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn new() -> Result<Self, Error> {
+        Self::new_in(crate::default_dir()).await
+    }
+
+    /// Creates a new temporary directory in the specified location.
+    /// When the instance goes out of scope, the directory will be deleted.
+    ///
+    /// The directory is created exclusively; if a directory with the random name already
+    /// exists, a new name is tried, up to 16 names in total.
+    ///
+    /// ## Arguments
+    ///
+    /// * `root_dir` - The root directory to create the directory in.
+    pub async fn new_in<P: Borrow<Path>>(root_dir: P) -> Result<Self, Error> {
+        let dir = root_dir.borrow();
+        check_directory_metadata(dir, ::async_std::fs::metadata(dir).await)?;
+        collision::retry(DEFAULT_ATTEMPTS, || {
+            Self::new_internal(dir.join(random_name(DIR_PREFIX)), Ownership::Owned, true)
+        })
+        .await
+    }
+
+    /// Creates a new temporary directory with the specified name in the default location.
+    /// When the instance goes out of scope, the directory will be deleted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The directory name to use.
+    pub async fn new_with_name<N: AsRef<str>>(name: N) -> Result<Self, Error> {
+        Self::new_with_name_in(name, crate::default_dir()).await
+    }
+
+    /// Creates a new temporary directory with the specified name in the specified location.
+    /// When the instance goes out of scope, the directory will be deleted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The directory name to use.
+    /// * `root_dir` - The root directory to create the directory in.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::NotADirectory`] or [`Error::DirectoryNotFound`] if the root directory
+    /// is not a directory or does not exist.
+    pub async fn new_with_name_in<N: AsRef<str>, P: Borrow<Path>>(
+        name: N,
+        root_dir: P,
+    ) -> Result<Self, Error> {
+        let dir = root_dir.borrow();
+        check_directory_metadata(dir, ::async_std::fs::metadata(dir).await)?;
+        Self::new_internal(dir.join(name.as_ref()), Ownership::Owned, false).await
+    }
+
+    /// Wraps a new instance of this type around an existing directory.
+    /// If `ownership` is set to [`Ownership::Borrowed`], this method does not take ownership of
+    /// the directory, i.e. the directory will not be deleted when the instance is dropped.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The path of the directory to wrap.
+    /// * `ownership` - The ownership of the directory.
+    pub async fn from_existing(path: PathBuf, ownership: Ownership) -> Result<Self, Error> {
+        check_directory_metadata(&path, ::async_std::fs::metadata(&path).await)?;
+        Self::new_internal(path, ownership, false).await
+    }
+
+    /// Returns the path of the underlying temporary directory.
+    pub fn dir_path(&self) -> &PathBuf {
+        &self.core.path
+    }
+
+    /// Determines the ownership of the temporary directory.
+    pub fn ownership(&self) -> Ownership {
+        self.core.ownership
+    }
+
+    /// Closes this reference to the directory and, if it was the last reference to an owned
+    /// directory, removes the directory and all of its contents.
+    ///
+    /// Unlike dropping the instance within a task, this waits for the removal, which is
+    /// performed on the blocking thread pool, and reports failures. If other references to
+    /// the directory remain, or if the directory is borrowed, this does nothing and succeeds.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::RemovalFailed`] with the paths that could not be removed and the error
+    /// encountered removing the first of them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::async_std::TempDir;
+    /// # async_std::task::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// let dir_path = dir.dir_path().clone();
+    /// async_std::fs::write(dir_path.join("file.txt"), "content").await?;
+    ///
+    /// dir.close().await?;
+    /// assert!(!dir_path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn close(self) -> Result<(), Error> {
+        let mut core = match Arc::try_unwrap(self.core) {
+            Ok(core) => core,
+            Err(_) => return Ok(()),
+        };
+
+        // The directory is removed here rather than when dropping the core.
+        if std::mem::replace(&mut core.ownership, Ownership::Borrowed) != Ownership::Owned {
+            return Ok(());
+        }
+
+        let path = core.path.clone();
+        let failed = task::spawn_blocking(move || removal::remove_dir_all(&path)).await;
+        trace::dir_deleted(&core.path, &failed);
+        removal::into_result(failed)
+    }
+
+    async fn new_internal(
+        path: PathBuf,
+        ownership: Ownership,
+        exclusive: bool,
+    ) -> Result<Self, Error> {
+        let (path, ownership) = task::spawn_blocking(move || {
+            creation::create_dir(&path, exclusive).map(|()| (path, ownership))
+        })
+        .await?;
+
+        trace::created("directory", &path, ownership);
+        Ok(Self {
+            core: Arc::new(TempDirCore { path, ownership }),
+        })
+    }
+}
+
+/// Ensures that the underlying directory is deleted if this is an owned instance.
+/// If the underlying directory is not owned, this operation does nothing.
+///
+/// Within a task, the directory is removed on the blocking thread pool; see [`TempDir::close`].
+impl Drop for TempDirCore {
+    fn drop(&mut self) {
+        if self.ownership == Ownership::Owned {
+            // Deleting all content recursively, continuing past entries that cannot be removed.
+            let path = std::mem::take(&mut self.path);
+            remove_off_executor(move || removal::remove_dir(&path));
+        }
+    }
+}
+
+impl Debug for TempDir {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.core.path)
+    }
+}
+
+/// Allows implicit treatment of TempDir as a Path.
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.core.path
+    }
+}
+
+impl Borrow<Path> for TempDir {
+    fn borrow(&self) -> &Path {
+        &self.core.path
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.core.path
+    }
+}
@@ -0,0 +1,328 @@
+use super::remove_off_executor;
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS, FILE_PREFIX};
+use crate::creation;
+use crate::removal;
+use crate::trace;
+use crate::{Error, Ownership};
+use ::async_std::fs::{File, OpenOptions};
+use ::async_std::io::{Read, Seek, SeekFrom, Write};
+use ::async_std::task;
+use std::borrow::Borrow;
+use std::fmt::{Debug, Formatter};
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// A named temporary file that will be cleaned automatically
+/// after the last reference to it is dropped, using `async-std` for I/O.
+pub struct TempFile {
+    /// A local reference to the file. Used to write to or read from the file.
+    file: ManuallyDrop<File>,
+
+    /// A shared pointer to the owned (or non-owned) file.
+    /// The `Arc` ensures that the enclosed file is kept alive
+    /// until all references to it are dropped.
+    core: ManuallyDrop<Arc<TempFileCore>>,
+}
+
+/// The instance that tracks the temporary file.
+/// If dropped, the file will be deleted.
+struct TempFileCore {
+    /// The path of the contained file.
+    path: PathBuf,
+
+    /// Whether the file is deleted on drop.
+    ownership: Ownership,
+}
+
+impl TempFile {
+    /// Creates a new temporary file in the default location.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// The default location is resolved via [`default_dir`](crate::default_dir) on every call.
+    pub async fn new() -> Result<Self, Error> {
+        Self::new_in(crate::default_dir()).await
+    }
+
+    /// Creates a new temporary file in the specified location.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// The file is created exclusively; if a file with the random name already exists,
+    /// a new name is tried, up to 16 names in total.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dir` - The directory to create the file in.
+    pub async fn new_in<P: Borrow<Path>>(dir: P) -> Result<Self, Error> {
+        let dir = dir.borrow();
+        collision::retry(DEFAULT_ATTEMPTS, || {
+            Self::create_with_name_in(random_name(FILE_PREFIX), dir, true)
+        })
+        .await
+    }
+
+    /// Creates a new temporary file with the specified name in the default location.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The file name to use.
+    pub async fn new_with_name<N: AsRef<str>>(name: N) -> Result<Self, Error> {
+        Self::new_with_name_in(name, crate::default_dir()).await
+    }
+
+    /// Creates a new temporary file with the specified name in the specified location.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The file name to use.
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is not a valid file name, and
+    /// [`Error::NotADirectory`] or [`Error::DirectoryNotFound`] if the directory
+    /// is not a directory or does not exist.
+    pub async fn new_with_name_in<N: AsRef<str>, P: Borrow<Path>>(
+        name: N,
+        dir: P,
+    ) -> Result<Self, Error> {
+        Self::create_with_name_in(name, dir.borrow(), false).await
+    }
+
+    /// Creates a new owned file with the specified name in the specified directory.
+    /// If `exclusive` is set, creation fails if the file already exists.
+    async fn create_with_name_in<N: AsRef<str>>(
+        name: N,
+        dir: &Path,
+        exclusive: bool,
+    ) -> Result<Self, Error> {
+        let name = name.as_ref().to_owned();
+        let dir = dir.to_path_buf();
+        let (file, path) =
+            task::spawn_blocking(move || creation::create_file(&name, &dir, exclusive)).await?;
+        Ok(Self::wrap(File::from(file), path, Ownership::Owned))
+    }
+
+    /// Wraps a new instance of this type around an existing file.
+    /// If `ownership` is set to [`Ownership::Borrowed`], this method does not take ownership of
+    /// the file, i.e. the file will not be deleted when the instance is dropped.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The path of the file to wrap.
+    /// * `ownership` - The ownership of the file.
+    pub async fn from_existing<P: Borrow<Path>>(
+        path: P,
+        ownership: Ownership,
+    ) -> Result<Self, Error> {
+        let path = path.borrow();
+        match ::async_std::fs::metadata(path).await {
+            Ok(metadata) if metadata.is_file() => {}
+            _ => return Err(Error::InvalidFile),
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .await
+            .map_err(|e| Error::io_at(e, path))?;
+        Ok(Self::wrap(file, path.to_path_buf(), ownership))
+    }
+
+    /// Returns the path of the underlying temporary file.
+    pub fn file_path(&self) -> &PathBuf {
+        &self.core.path
+    }
+
+    /// Determines the ownership of the temporary file.
+    pub fn ownership(&self) -> Ownership {
+        self.core.ownership
+    }
+
+    /// Opens a new TempFile instance in read-write mode.
+    ///
+    /// The new instance has its own cursor and keeps the file alive like the existing one.
+    pub async fn open_rw(&self) -> Result<TempFile, Error> {
+        self.open(true).await
+    }
+
+    /// Opens a new TempFile instance in read-only mode.
+    ///
+    /// Like [`TempFile::open_rw`], the instance has its own cursor.
+    pub async fn open_ro(&self) -> Result<TempFile, Error> {
+        self.open(false).await
+    }
+
+    async fn open(&self, write: bool) -> Result<TempFile, Error> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(write)
+            .open(&self.core.path)
+            .await
+            .map_err(|e| Error::io_at(e, &self.core.path))?;
+        Ok(TempFile {
+            file: ManuallyDrop::new(file),
+            core: self.core.clone(),
+        })
+    }
+
+    /// Closes this reference to the file and, if it was the last reference to an owned file,
+    /// deletes the file.
+    ///
+    /// Unlike dropping the instance within a task, this waits for the deletion, which is
+    /// performed on the blocking thread pool, and reports failures. If other references to
+    /// the file remain, or if the file is borrowed, this does nothing and succeeds.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if the file could not be deleted. The file is not deleted again
+    /// afterwards.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::async_std::TempFile;
+    /// # async_std::task::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// let path = file.file_path().clone();
+    ///
+    /// let reader = file.open_ro().await?;
+    /// reader.close().await?;
+    /// assert!(path.exists());
+    ///
+    /// file.close().await?;
+    /// assert!(!path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn close(self) -> Result<(), Error> {
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used again and its Drop implementation does not run.
+        let core = unsafe {
+            ManuallyDrop::drop(&mut this.file);
+            ManuallyDrop::take(&mut this.core)
+        };
+
+        let mut core = match Arc::try_unwrap(core) {
+            Ok(core) => core,
+            Err(_) => return Ok(()),
+        };
+
+        // The file is deleted here rather than when dropping the core.
+        if std::mem::replace(&mut core.ownership, Ownership::Borrowed) != Ownership::Owned {
+            return Ok(());
+        }
+
+        let path = core.path.clone();
+        let result = task::spawn_blocking(move || std::fs::remove_file(path)).await;
+        trace::file_deleted(&core.path, &result);
+        result.map_err(|e| Error::io_at(e, &core.path))
+    }
+
+    fn wrap(file: File, path: PathBuf, ownership: Ownership) -> Self {
+        trace::created("file", &path, ownership);
+        Self {
+            file: ManuallyDrop::new(file),
+            core: ManuallyDrop::new(Arc::new(TempFileCore { path, ownership })),
+        }
+    }
+}
+
+/// Ensures the file handles are closed before the core reference is freed.
+/// If the core reference would be freed while handles are still open, it is
+/// possible that the underlying file cannot be deleted.
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // Ensure all file handles are closed before we attempt to delete the file itself via core.
+        drop(unsafe { ManuallyDrop::take(&mut self.file) });
+        drop(unsafe { ManuallyDrop::take(&mut self.core) });
+    }
+}
+
+/// Ensures that the underlying file is deleted if this is an owned instance.
+/// If the underlying file is not owned, this operation does nothing.
+///
+/// Within a task, the file is deleted on the blocking thread pool; see [`TempFile::close`].
+impl Drop for TempFileCore {
+    fn drop(&mut self) {
+        if self.ownership == Ownership::Owned {
+            let path = std::mem::take(&mut self.path);
+            remove_off_executor(move || removal::remove_file(&path));
+        }
+    }
+}
+
+impl Debug for TempFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.core.path)
+    }
+}
+
+/// Allows implicit treatment of TempFile as a File.
+impl Deref for TempFile {
+    type Target = File;
+
+    fn deref(&self) -> &Self::Target {
+        &self.file
+    }
+}
+
+/// Allows implicit treatment of TempFile as a mutable File.
+impl DerefMut for TempFile {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.file
+    }
+}
+
+impl Borrow<File> for TempFile {
+    fn borrow(&self) -> &File {
+        &self.file
+    }
+}
+
+/// Forwarding Read to the embedded File
+impl Read for TempFile {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(self.file.deref_mut()).poll_read(cx, buf)
+    }
+}
+
+/// Forwarding Write to the embedded File
+impl Write for TempFile {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(self.file.deref_mut()).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(self.file.deref_mut()).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(self.file.deref_mut()).poll_close(cx)
+    }
+}
+
+/// Forwarding Seek to the embedded File
+impl Seek for TempFile {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        Pin::new(self.file.deref_mut()).poll_seek(cx, pos)
+    }
+}
@@ -0,0 +1,234 @@
+//! Synchronous temporary files for code that does not run on an async runtime.
+//!
+//! The [`TempFile`] of this module wraps a [`std::fs::File`] and performs all operations,
+//! including the deletion on drop, on the calling thread. It shares [`Ownership`] and
+//! [`Error`] with the asynchronous types, as well as the naming of generated files.
+//!
+//! ```
+//! use async_tempfile::blocking::TempFile;
+//! use std::io::{Read, Seek, Write};
+//!
+//! let mut file = TempFile::new()?;
+//! file.write_all(b"data")?;
+//! file.rewind()?;
+//!
+//! let mut content = String::new();
+//! file.read_to_string(&mut content)?;
+//! assert_eq!(content, "data");
+//!
+//! let path = file.file_path().clone();
+//! drop(file);
+//! assert!(!path.exists());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS, FILE_PREFIX};
+use crate::creation;
+use crate::removal;
+use crate::trace;
+use crate::{Error, Ownership};
+use std::borrow::Borrow;
+use std::fmt::{Debug, Formatter};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+/// A named temporary file that is deleted when dropped, using blocking I/O.
+///
+/// Unlike the asynchronous [`TempFile`](crate::TempFile), instances are not shared between
+/// multiple references; the file is deleted when this instance is dropped, unless it is
+/// borrowed.
+pub struct TempFile {
+    /// The handle to the file, closed before the file is deleted.
+    file: ManuallyDrop<File>,
+
+    /// The path of the file.
+    path: PathBuf,
+
+    /// Whether the file is deleted on drop.
+    ownership: Ownership,
+}
+
+impl TempFile {
+    /// Creates a new temporary file in the default location.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// The default location is resolved via [`default_dir`](crate::default_dir) on every call.
+    pub fn new() -> Result<Self, Error> {
+        Self::new_in(crate::default_dir())
+    }
+
+    /// Creates a new temporary file in the specified location.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// The file is created exclusively; if a file with the random name already exists,
+    /// a new name is tried, up to 16 names in total.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dir` - The directory to create the file in.
+    pub fn new_in<P: Borrow<Path>>(dir: P) -> Result<Self, Error> {
+        let dir = dir.borrow();
+        collision::retry_blocking(DEFAULT_ATTEMPTS, || {
+            Self::create_with_name_in(random_name(FILE_PREFIX), dir, true)
+        })
+    }
+
+    /// Creates a new temporary file with the specified name in the default location.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The file name to use.
+    pub fn new_with_name<N: AsRef<str>>(name: N) -> Result<Self, Error> {
+        Self::new_with_name_in(name, crate::default_dir())
+    }
+
+    /// Creates a new temporary file with the specified name in the specified location.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The file name to use.
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is not a valid file name, and
+    /// [`Error::NotADirectory`] or [`Error::DirectoryNotFound`] if the directory
+    /// is not a directory or does not exist.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::blocking::TempFile;
+    /// let file = TempFile::new_with_name_in("data.bin", std::env::temp_dir())?;
+    /// assert!(file.file_path().ends_with("data.bin"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_with_name_in<N: AsRef<str>, P: Borrow<Path>>(
+        name: N,
+        dir: P,
+    ) -> Result<Self, Error> {
+        Self::create_with_name_in(name, dir.borrow(), false)
+    }
+
+    /// Wraps a new instance of this type around an existing file.
+    /// If `ownership` is set to [`Ownership::Borrowed`], this method does not take ownership of
+    /// the file, i.e. the file will not be deleted when the instance is dropped.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The path of the file to wrap.
+    /// * `ownership` - The ownership of the file.
+    pub fn from_existing<P: Borrow<Path>>(path: P, ownership: Ownership) -> Result<Self, Error> {
+        let path = path.borrow();
+        match std::fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => {}
+            _ => return Err(Error::InvalidFile),
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| Error::io_at(e, path))?;
+        Ok(Self::wrap(file, path.to_path_buf(), ownership))
+    }
+
+    /// Returns the path of the underlying temporary file.
+    pub fn file_path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Determines the ownership of the temporary file.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// Creates a new owned file with the specified name in the specified directory.
+    /// If `exclusive` is set, creation fails if the file already exists.
+    fn create_with_name_in<N: AsRef<str>>(
+        name: N,
+        dir: &Path,
+        exclusive: bool,
+    ) -> Result<Self, Error> {
+        let (file, path) = creation::create_file(name.as_ref(), dir, exclusive)?;
+        Ok(Self::wrap(file, path, Ownership::Owned))
+    }
+
+    fn wrap(file: File, path: PathBuf, ownership: Ownership) -> Self {
+        trace::created("file", &path, ownership);
+        Self {
+            file: ManuallyDrop::new(file),
+            path,
+            ownership,
+        }
+    }
+}
+
+/// Ensures the file handle is closed before the file is deleted.
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // SAFETY: The file is never used again.
+        drop(unsafe { ManuallyDrop::take(&mut self.file) });
+
+        if self.ownership == Ownership::Owned {
+            removal::remove_file(&self.path);
+        }
+    }
+}
+
+impl Debug for TempFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.path)
+    }
+}
+
+/// Allows implicit treatment of TempFile as a File.
+impl Deref for TempFile {
+    type Target = File;
+
+    fn deref(&self) -> &Self::Target {
+        &self.file
+    }
+}
+
+/// Allows implicit treatment of TempFile as a mutable File.
+impl DerefMut for TempFile {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.file
+    }
+}
+
+impl Borrow<File> for TempFile {
+    fn borrow(&self) -> &File {
+        &self.file
+    }
+}
+
+/// Forwarding Read to the embedded File
+impl Read for TempFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+/// Forwarding Write to the embedded File
+impl Write for TempFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Forwarding Seek to the embedded File
+impl Seek for TempFile {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.file.seek(pos)
+    }
+}
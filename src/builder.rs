@@ -0,0 +1,149 @@
+use crate::collision::{random_name, DEFAULT_ATTEMPTS};
+use crate::{Error, TempFile};
+use std::borrow::Borrow;
+use std::path::{Path, PathBuf};
+
+/// A builder for temporary files with a custom name pattern and location.
+///
+/// Created via [`TempFile::builder`]. The generated file name is
+/// `{prefix}{random}{suffix}.{extension}`, where the random part is a UUID if the `uuid`
+/// feature is enabled. Without an extension, the name has no trailing dot.
+///
+/// ## Example
+///
+/// ```
+/// # use async_tempfile::TempFile;
+/// # let _ = tokio_test::block_on(async {
+/// let file = TempFile::builder()
+///     .prefix("report_")
+///     .suffix("_draft")
+///     .extension("json")
+///     .build()
+///     .await?;
+///
+/// let name = file.file_path().file_name().unwrap().to_str().unwrap();
+/// assert!(name.starts_with("report_"));
+/// assert!(name.ends_with("_draft.json"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct TempFileBuilder {
+    /// The text preceding the random part of the name.
+    prefix: String,
+
+    /// The text following the random part of the name.
+    suffix: String,
+
+    /// The extension of the file, without the leading dot.
+    extension: Option<String>,
+
+    /// The directory to create the file in, or `None` for the default location.
+    dir: Option<PathBuf>,
+
+    /// Whether to flush pending writes when references to the file are dropped.
+    flush_on_drop: bool,
+
+    /// Whether to overwrite the contents of the file before deleting it.
+    shred_on_drop: bool,
+
+    /// The number of names to try if a file with the generated name exists.
+    max_attempts: u32,
+}
+
+impl TempFileBuilder {
+    pub(crate) fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_owned(),
+            suffix: String::new(),
+            extension: None,
+            dir: None,
+            flush_on_drop: false,
+            shred_on_drop: false,
+            max_attempts: DEFAULT_ATTEMPTS,
+        }
+    }
+
+    /// Sets the text preceding the random part of the file name.
+    ///
+    /// Defaults to the prefix used by [`TempFile::new`].
+    pub fn prefix<S: AsRef<str>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the text following the random part of the file name, preceding the extension.
+    ///
+    /// Defaults to an empty suffix.
+    pub fn suffix<S: AsRef<str>>(mut self, suffix: S) -> Self {
+        self.suffix = suffix.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the extension of the file name, without the leading dot.
+    ///
+    /// By default, the file name has no extension.
+    pub fn extension<S: AsRef<str>>(mut self, extension: S) -> Self {
+        self.extension = Some(extension.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the directory to create the file in.
+    ///
+    /// By default, the file is created in the same location as by [`TempFile::new`].
+    pub fn dir<P: Borrow<Path>>(mut self, dir: P) -> Self {
+        self.dir = Some(dir.borrow().to_path_buf());
+        self
+    }
+
+    /// Sets whether pending writes are flushed when references to the file are dropped.
+    ///
+    /// Disabled by default. See [`TempFile::set_flush_on_drop`] for details.
+    pub fn flush_on_drop(mut self, enabled: bool) -> Self {
+        self.flush_on_drop = enabled;
+        self
+    }
+
+    /// Sets whether the contents of the file are overwritten before it is deleted.
+    ///
+    /// Disabled by default. See [`TempFile::set_shred_on_drop`] for details.
+    pub fn shred_on_drop(mut self, enabled: bool) -> Self {
+        self.shred_on_drop = enabled;
+        self
+    }
+
+    /// Sets the number of names to try if a file with the generated name already exists.
+    ///
+    /// Existing files are never reused; on a collision, the random part of the name is
+    /// generated anew. Defaults to 16 attempts; a value of zero is treated as one.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Creates the file. When the instance goes out of scope, the file will be deleted.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the prefix, suffix or extension contain path
+    /// separators or the resulting file name is otherwise invalid, and [`Error::Io`] with
+    /// [`std::io::ErrorKind::AlreadyExists`] if all attempted names were taken.
+    pub async fn build(self) -> Result<TempFile, Error> {
+        let file =
+            TempFile::create_unique(self.dir.as_deref(), self.max_attempts, || self.file_name())
+                .await?;
+        file.set_flush_on_drop(self.flush_on_drop);
+        file.set_shred_on_drop(self.shred_on_drop);
+        Ok(file)
+    }
+
+    /// Generates a file name following the configured pattern.
+    fn file_name(&self) -> String {
+        let mut name = format!("{}{}", random_name(&self.prefix), self.suffix);
+        if let Some(extension) = &self.extension {
+            name.push('.');
+            name.push_str(extension);
+        }
+        name
+    }
+}
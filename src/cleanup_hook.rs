@@ -0,0 +1,57 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// The callback invoked when a file or directory could not be deleted on drop.
+type CleanupHook = Box<dyn Fn(&Path, &std::io::Error) + Send + Sync>;
+
+/// The callback registered via [`on_cleanup_error`], if any.
+static CLEANUP_HOOK: OnceLock<CleanupHook> = OnceLock::new();
+
+/// Registers a process-wide callback that is invoked when an owned temporary file or directory
+/// could not be deleted on drop, e.g. to count leaked files in metrics.
+///
+/// Dropping cannot report errors, so they are otherwise ignored. The callback is invoked with
+/// the path that could not be removed and the error encountered; for directories, it is invoked
+/// once for each entry that could not be removed. Files that were already deleted by someone
+/// else are not reported. Failures reported through the return value
+/// of [`TempFile::close`] or [`TempDir::close`] do not invoke it.
+///
+/// The callback runs synchronously on the thread dropping the last reference, which may be a
+/// worker thread of the async runtime, so it should return quickly and must not panic, as
+/// panicking in a drop may abort the process.
+///
+/// The callback can only be registered once. Returns `false` if a callback was registered
+/// before, in which case `hook` is dropped without being registered.
+///
+/// ## Arguments
+///
+/// * `hook` - The callback to invoke.
+///
+/// ## Example
+///
+/// ```
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// static LEAKED: AtomicUsize = AtomicUsize::new(0);
+///
+/// async_tempfile::on_cleanup_error(|path, error| {
+///     LEAKED.fetch_add(1, Ordering::Relaxed);
+///     eprintln!("failed to delete {}: {error}", path.display());
+/// });
+/// ```
+///
+/// [`TempFile::close`]: crate::TempFile::close
+/// [`TempDir::close`]: crate::TempDir::close
+pub fn on_cleanup_error<F>(hook: F) -> bool
+where
+    F: Fn(&Path, &std::io::Error) + Send + Sync + 'static,
+{
+    CLEANUP_HOOK.set(Box::new(hook)).is_ok()
+}
+
+/// Invokes the registered callback, if any.
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "blocking"))]
+pub(crate) fn report(path: &Path, error: &std::io::Error) {
+    if let Some(hook) = CLEANUP_HOOK.get() {
+        hook(path, error);
+    }
+}
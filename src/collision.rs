@@ -0,0 +1,130 @@
+//! Generation of random names and the retry of creations that collide with existing entries.
+
+#[cfg(not(feature = "uuid"))]
+use crate::random_name::RandomName;
+use crate::{Error, ErrorKind};
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+use std::future::Future;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+/// The prefix of the names generated for temporary files.
+pub(crate) const FILE_PREFIX: &str = "atmp_";
+
+/// The prefix of the names generated for temporary directories.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+pub(crate) const DIR_PREFIX: &str = "atmpd_";
+
+/// The number of names tried when creating a file with a random name, unless configured
+/// otherwise via [`TempFileBuilder::max_attempts`](crate::TempFileBuilder::max_attempts).
+pub(crate) const DEFAULT_ATTEMPTS: u32 = 16;
+
+/// Generates a random file name with the specified prefix.
+pub(crate) fn random_name(prefix: &str) -> String {
+    #[cfg(feature = "uuid")]
+    {
+        format!("{}{}", prefix, Uuid::new_v4())
+    }
+
+    #[cfg(not(feature = "uuid"))]
+    {
+        RandomName::new(prefix).as_str().to_owned()
+    }
+}
+
+/// Runs `create` until it succeeds, fails with an error other than
+/// [`ErrorKind::AlreadyExists`], or was run `attempts` times.
+///
+/// `create` is expected to pick a new name on every call and to create the entry exclusively.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+pub(crate) async fn retry<T, F, Fut>(attempts: u32, mut create: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match create().await {
+            Err(e) if e.kind() == ErrorKind::AlreadyExists && attempt < attempts => {
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Like [`retry`], for creations using blocking I/O.
+#[cfg(feature = "blocking")]
+pub(crate) fn retry_blocking<T, F>(attempts: u32, mut create: F) -> Result<T, Error>
+where
+    F: FnMut() -> Result<T, Error>,
+{
+    let mut attempt = 1;
+    loop {
+        match create() {
+            Err(e) if e.kind() == ErrorKind::AlreadyExists && attempt < attempts => {
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "tokio", feature = "async-std")))]
+mod tests {
+    use super::*;
+    use crate::creation;
+    use crate::random_name::RandomName;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    fn frozen() -> Duration {
+        Duration::new(0x1234, 0x5678)
+    }
+
+    /// Yields every count twice, so that names repeat once the clock is frozen.
+    fn stuttering() -> u64 {
+        static COUNT: AtomicU64 = AtomicU64::new(0);
+        COUNT.fetch_add(1, Ordering::SeqCst) / 2
+    }
+
+    fn fixed() -> u64 {
+        0
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_duplicate_names() {
+        let dir = std::env::temp_dir().join(format!("atmpd_collision_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let create = |attempts: &mut u32, counter| {
+            *attempts += 1;
+            let name = RandomName::with_sources(FILE_PREFIX, frozen, counter);
+            let result = creation::create_file(name.as_str(), &dir, true);
+            async move { result.map(|(_, path)| path) }
+        };
+
+        let mut attempts = 0;
+        let first = retry(DEFAULT_ATTEMPTS, || create(&mut attempts, stuttering))
+            .await
+            .unwrap();
+        assert_eq!(attempts, 1);
+
+        // The second name repeats the first one, so a new name is tried.
+        let mut attempts = 0;
+        let second = retry(DEFAULT_ATTEMPTS, || create(&mut attempts, stuttering))
+            .await
+            .unwrap();
+        assert_eq!(attempts, 2);
+        assert_ne!(first, second);
+
+        // With a fixed counter, all names collide and the retry gives up.
+        let mut attempts = 0;
+        let error = retry(DEFAULT_ATTEMPTS, || create(&mut attempts, fixed))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(attempts, DEFAULT_ATTEMPTS);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+use crate::TempFile;
+use std::borrow::{Borrow, BorrowMut};
+use std::fmt::{Debug, Formatter};
+use std::io::{IoSlice, SeekFrom};
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+
+/// A [`TempFile`] wrapper that keeps track of the number of bytes written through it.
+///
+/// Created via [`TempFile::counting`]. The count includes every byte accepted by
+/// [`AsyncWrite::poll_write`] and [`AsyncWrite::poll_write_vectored`], regardless of
+/// whether it was already flushed to the file system. Seeking does not affect the count.
+pub struct CountingTempFile {
+    /// The wrapped file.
+    file: TempFile,
+
+    /// The number of bytes written so far.
+    bytes_written: u64,
+}
+
+impl CountingTempFile {
+    pub(crate) fn new(file: TempFile) -> Self {
+        Self {
+            file,
+            bytes_written: 0,
+        }
+    }
+
+    /// Returns the number of bytes written through this wrapper so far.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncWriteExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?.counting();
+    /// file.write_all(b"hello").await?;
+    /// file.write_all(b", world").await?;
+    /// assert_eq!(file.bytes_written(), 12);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Unwraps the inner [`TempFile`].
+    pub fn into_inner(self) -> TempFile {
+        self.file
+    }
+}
+
+impl Debug for CountingTempFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CountingTempFile")
+            .field("file", &self.file)
+            .field("bytes_written", &self.bytes_written)
+            .finish()
+    }
+}
+
+/// Allows implicit treatment of CountingTempFile as a TempFile.
+impl Deref for CountingTempFile {
+    type Target = TempFile;
+
+    fn deref(&self) -> &Self::Target {
+        &self.file
+    }
+}
+
+/// Allows implicit treatment of CountingTempFile as a mutable TempFile.
+impl DerefMut for CountingTempFile {
+    fn deref_mut(&mut self) -> &mut TempFile {
+        &mut self.file
+    }
+}
+
+impl Borrow<TempFile> for CountingTempFile {
+    fn borrow(&self) -> &TempFile {
+        &self.file
+    }
+}
+
+impl BorrowMut<TempFile> for CountingTempFile {
+    fn borrow_mut(&mut self) -> &mut TempFile {
+        &mut self.file
+    }
+}
+
+impl AsRef<TempFile> for CountingTempFile {
+    fn as_ref(&self) -> &TempFile {
+        &self.file
+    }
+}
+
+impl From<CountingTempFile> for TempFile {
+    fn from(value: CountingTempFile) -> Self {
+        value.into_inner()
+    }
+}
+
+/// Forwarding AsyncWrite to the embedded TempFile, counting the written bytes.
+impl AsyncWrite for CountingTempFile {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, std::io::Error>> {
+        let poll = Pin::new(&mut self.file).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = poll {
+            self.bytes_written += written as u64;
+        }
+        poll
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), std::io::Error>> {
+        Pin::new(&mut self.file).poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), std::io::Error>> {
+        Pin::new(&mut self.file).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize, std::io::Error>> {
+        let poll = Pin::new(&mut self.file).poll_write_vectored(cx, bufs);
+        if let Poll::Ready(Ok(written)) = poll {
+            self.bytes_written += written as u64;
+        }
+        poll
+    }
+}
+
+/// Forwarding AsyncRead to the embedded TempFile
+impl AsyncRead for CountingTempFile {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.file).poll_read(cx, buf)
+    }
+}
+
+/// Forwarding AsyncSeek to the embedded TempFile
+impl AsyncSeek for CountingTempFile {
+    fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        Pin::new(&mut self.file).start_seek(position)
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        Pin::new(&mut self.file).poll_complete(cx)
+    }
+}
@@ -0,0 +1,63 @@
+//! Creation of temporary files and directories, shared by the types of all runtimes.
+//!
+//! The functions use blocking I/O; the asynchronous types run them on the blocking thread pool
+//! of their runtime.
+
+use crate::errors::check_directory_metadata;
+use crate::file_name::is_valid_file_name;
+use crate::Error;
+use std::ffi::OsStr;
+use std::fs::{File, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Creates the file `name` in `dir`, opened for reading and writing, and returns it along
+/// with its path. If `exclusive` is set, creation fails if the file already exists;
+/// otherwise, an existing file is opened without truncating it.
+///
+/// ## Errors
+///
+/// Returns [`Error::InvalidFile`] if the name is not a valid file name, and
+/// [`Error::NotADirectory`] or [`Error::DirectoryNotFound`] if the directory
+/// is not a directory or does not exist.
+pub(crate) fn create_file(
+    name: &str,
+    dir: &Path,
+    exclusive: bool,
+) -> Result<(File, PathBuf), Error> {
+    if !is_valid_file_name(OsStr::new(name)) {
+        return Err(Error::InvalidFile);
+    }
+    let path = dir.join(name);
+
+    let mut options = OpenOptions::new();
+    options.read(true).write(true);
+    if exclusive {
+        options.create_new(true);
+    } else {
+        options.create(true).truncate(false);
+    }
+
+    // Rather than checking the directory upfront (which would race with the file creation),
+    // we attempt to create the file and interpret the failure.
+    match options.open(&path) {
+        Ok(file) => Ok((file, path)),
+        Err(error) if matches!(error.kind(), ErrorKind::NotFound | ErrorKind::NotADirectory) => {
+            check_directory_metadata(dir, std::fs::metadata(dir))?;
+            Err(Error::io_at(error, path))
+        }
+        Err(error) => Err(Error::io_at(error, path)),
+    }
+}
+
+/// Creates the directory at `path`. If `exclusive` is set, creation fails if the directory
+/// already exists; otherwise, the directory is created along with all its parents.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+pub(crate) fn create_dir(path: &Path, exclusive: bool) -> Result<(), Error> {
+    let result = if exclusive {
+        std::fs::create_dir(path)
+    } else {
+        std::fs::create_dir_all(path)
+    };
+    result.map_err(|e| Error::io_at(e, path))
+}
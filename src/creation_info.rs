@@ -0,0 +1,67 @@
+use crate::Ownership;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// Describes how a [`TempFile`](crate::TempFile) was created.
+///
+/// Obtained via [`TempFile::creation_info`](crate::TempFile::creation_info). The information
+/// reflects the state at creation time; later changes, e.g. renaming the file or changing
+/// its ownership, are not reflected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreationInfo {
+    directory: PathBuf,
+    name: OsString,
+    ownership: Ownership,
+    mode: Option<u32>,
+}
+
+impl CreationInfo {
+    /// Captures the creation parameters of the file at the specified path.
+    pub(crate) fn new(path: &Path, ownership: Ownership) -> Self {
+        Self {
+            directory: path.parent().map(PathBuf::from).unwrap_or_default(),
+            name: path.file_name().map(OsString::from).unwrap_or_default(),
+            ownership,
+            mode: None,
+        }
+    }
+
+    /// Captures the creation parameters of an unnamed file in the specified directory.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn new_unnamed(directory: &Path, ownership: Ownership) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+            name: OsString::new(),
+            ownership,
+            mode: None,
+        }
+    }
+
+    /// Returns the directory the file was created in.
+    ///
+    /// For files created through a `cap-std` directory capability, this is
+    /// relative to the capability and thus usually empty.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the name the file was created with.
+    ///
+    /// This is empty for anonymous files.
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    /// Returns the ownership the file was created with.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// Returns the permission mode explicitly requested for the file, if any.
+    ///
+    /// Files are currently created with the platform's default permissions,
+    /// in which case this is `None`.
+    pub fn mode(&self) -> Option<u32> {
+        self.mode
+    }
+}
@@ -1,29 +1,324 @@
 use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub enum Error {
     /// An invalid or missing directory was specified.
     InvalidDirectory,
+    /// The specified path exists, but is not a directory.
+    NotADirectory(PathBuf),
+    /// The specified directory does not exist.
+    DirectoryNotFound(PathBuf),
     /// An invalid or missing file was specified.
     InvalidFile,
+    /// The temporary file no longer exists, e.g. because it was deleted externally.
+    FileVanished(PathBuf),
+    /// The operation is not supported on the current platform.
+    Unsupported,
+    /// Removing a directory failed.
+    RemovalFailed {
+        /// The paths that could not be removed.
+        paths: Vec<PathBuf>,
+        /// The error encountered removing the first of the paths.
+        source: std::io::Error,
+    },
+    /// The file system does not have enough free space for the operation, or a disk quota
+    /// was exceeded.
+    ///
+    /// I/O errors of kind [`StorageFull`](std::io::ErrorKind::StorageFull) or
+    /// [`QuotaExceeded`](std::io::ErrorKind::QuotaExceeded), e.g. `ENOSPC` and `EDQUOT` on Unix,
+    /// are reported as this variant by all operations of this crate returning an [`Error`],
+    /// e.g. when creating or writing to a file, so that callers can apply backpressure.
+    OutOfSpace {
+        /// The underlying I/O error.
+        source: std::io::Error,
+        /// The path of the file or directory involved, if known.
+        path: Option<PathBuf>,
+    },
     /// An I/O error occurred.
-    Io(std::io::Error),
+    Io {
+        /// The underlying I/O error.
+        source: std::io::Error,
+        /// The path of the file or directory involved, if known.
+        path: Option<PathBuf>,
+    },
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::InvalidDirectory => write!(f, "An invalid directory was specified"),
+            Self::NotADirectory(path) => {
+                write!(f, "The path {} is not a directory", path.display())
+            }
+            Self::DirectoryNotFound(path) => {
+                write!(f, "The directory {} does not exist", path.display())
+            }
             Self::InvalidFile => write!(f, "An invalid file name was specified"),
-            Self::Io(e) => Display::fmt(e, f),
+            Self::FileVanished(path) => {
+                write!(f, "The file {} no longer exists", path.display())
+            }
+            Self::Unsupported => write!(f, "The operation is not supported on this platform"),
+            Self::RemovalFailed { paths, .. } => {
+                write!(f, "Failed to remove {} path(s)", paths.len())?;
+                if let Some(path) = paths.first() {
+                    write!(f, ", including {}", path.display())?;
+                }
+                Ok(())
+            }
+            Self::OutOfSpace { path: None, .. } => {
+                write!(f, "Not enough free space on the file system")
+            }
+            Self::OutOfSpace {
+                path: Some(path), ..
+            } => write!(
+                f,
+                "{}: Not enough free space on the file system",
+                path.display()
+            ),
+            Self::Io { source, path: None } => Display::fmt(source, f),
+            Self::Io {
+                source,
+                path: Some(path),
+            } => write!(f, "{}: {}", path.display(), source),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. }
+            | Self::OutOfSpace { source, .. }
+            | Self::RemovalFailed { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A coarse classification of an [`Error`], e.g. for mapping errors to HTTP status codes.
+///
+/// Obtained via [`Error::kind`]. The underlying error remains accessible through
+/// [`std::error::Error::source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A file or directory was not found.
+    NotFound,
+    /// The permissions were insufficient for the operation.
+    PermissionDenied,
+    /// A file or directory already exists.
+    AlreadyExists,
+    /// The file system ran out of space or a quota was exceeded.
+    OutOfSpace,
+    /// Any other error.
+    Other,
+}
+
+impl Error {
+    /// Classifies the error, based on the variant and the underlying I/O error, if any.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{Error, ErrorKind, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let error = TempFile::new_in(std::env::temp_dir().join("missing"))
+    ///     .await
+    ///     .unwrap_err();
+    /// let status = match error.kind() {
+    ///     ErrorKind::NotFound => 404,
+    ///     ErrorKind::PermissionDenied => 403,
+    ///     ErrorKind::AlreadyExists => 409,
+    ///     ErrorKind::OutOfSpace => 507,
+    ///     _ => 500,
+    /// };
+    /// assert_eq!(status, 404);
+    /// # });
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InvalidDirectory | Self::DirectoryNotFound(_) | Self::FileVanished(_) => {
+                ErrorKind::NotFound
+            }
+            Self::NotADirectory(_)
+            | Self::InvalidFile
+            | Self::Unsupported
+            | Self::RemovalFailed { .. } => ErrorKind::Other,
+            Self::OutOfSpace { .. } => ErrorKind::OutOfSpace,
+            Self::Io { source, .. } => match source.kind() {
+                std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+                std::io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+                std::io::ErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+                std::io::ErrorKind::StorageFull | std::io::ErrorKind::QuotaExceeded => {
+                    ErrorKind::OutOfSpace
+                }
+                _ => ErrorKind::Other,
+            },
+        }
+    }
+}
 
+impl Error {
+    /// Returns the path of the file or directory involved in the error, if known.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = std::env::temp_dir().join("missing");
+    /// let error = TempFile::new_in(dir.as_path()).await.unwrap_err();
+    /// assert_eq!(error.path(), Some(dir.as_path()));
+    /// # });
+    /// ```
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::NotADirectory(path)
+            | Self::DirectoryNotFound(path)
+            | Self::FileVanished(path) => Some(path),
+            Self::Io { path, .. } | Self::OutOfSpace { path, .. } => path.as_deref(),
+            Self::RemovalFailed { paths, .. } => paths.first().map(PathBuf::as_path),
+            _ => None,
+        }
+    }
+
+    /// Creates an [`Error::Io`] involving the specified path.
+    /// Errors indicating a full file system are reported as [`Error::OutOfSpace`] instead.
+    #[cfg(any(test, feature = "tokio", feature = "async-std", feature = "blocking"))]
+    pub(crate) fn io_at<P: Into<PathBuf>>(source: std::io::Error, path: P) -> Self {
+        let path = Some(path.into());
+        if is_out_of_space(&source) {
+            return Self::OutOfSpace { source, path };
+        }
+        Self::Io { source, path }
+    }
+}
+
+/// Converts an I/O error into an [`Error::Io`], or into an [`Error::OutOfSpace`] if it
+/// indicates a full file system.
 impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Self::Io(e)
+    fn from(source: std::io::Error) -> Self {
+        if is_out_of_space(&source) {
+            return Self::OutOfSpace { source, path: None };
+        }
+        Self::Io { source, path: None }
+    }
+}
+
+/// Determines whether the error indicates that the file system ran out of space
+/// or that a quota was exceeded.
+fn is_out_of_space(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        std::io::ErrorKind::StorageFull | std::io::ErrorKind::QuotaExceeded
+    )
+}
+
+/// Ensures that the specified path exists and is a directory.
+///
+/// ## Errors
+///
+/// Returns [`Error::NotADirectory`] if the path exists but is not a directory,
+/// and [`Error::DirectoryNotFound`] if it does not exist.
+#[cfg(feature = "tokio")]
+pub(crate) async fn check_directory(path: &Path) -> Result<(), Error> {
+    check_directory_metadata(path, tokio::fs::metadata(path).await)
+}
+
+/// Like [`check_directory`], operating on the result of a metadata query of the path
+/// performed by the caller.
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "blocking"))]
+pub(crate) fn check_directory_metadata(
+    path: &Path,
+    metadata: std::io::Result<std::fs::Metadata>,
+) -> Result<(), Error> {
+    match metadata {
+        Ok(metadata) if metadata.is_dir() => Ok(()),
+        Ok(_) => Err(Error::NotADirectory(path.to_path_buf())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(Error::DirectoryNotFound(path.to_path_buf()))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind() {
+        let io = |kind: std::io::ErrorKind| Error::from(std::io::Error::from(kind));
+        assert_eq!(io(std::io::ErrorKind::NotFound).kind(), ErrorKind::NotFound);
+        assert_eq!(
+            io(std::io::ErrorKind::PermissionDenied).kind(),
+            ErrorKind::PermissionDenied
+        );
+        assert_eq!(
+            io(std::io::ErrorKind::AlreadyExists).kind(),
+            ErrorKind::AlreadyExists
+        );
+        assert_eq!(
+            io(std::io::ErrorKind::StorageFull).kind(),
+            ErrorKind::OutOfSpace
+        );
+        assert_eq!(io(std::io::ErrorKind::Interrupted).kind(), ErrorKind::Other);
+        assert_eq!(
+            Error::DirectoryNotFound(PathBuf::new()).kind(),
+            ErrorKind::NotFound
+        );
+        assert_eq!(Error::InvalidFile.kind(), ErrorKind::Other);
+        assert_eq!(
+            io(std::io::ErrorKind::QuotaExceeded).kind(),
+            ErrorKind::OutOfSpace
+        );
+    }
+
+    #[test]
+    fn test_out_of_space() {
+        use std::error::Error as _;
+
+        let error = Error::from(std::io::Error::from(std::io::ErrorKind::StorageFull));
+        assert!(matches!(error, Error::OutOfSpace { .. }));
+        assert_eq!(error.path(), None);
+
+        let error = Error::io_at(
+            std::io::Error::from(std::io::ErrorKind::QuotaExceeded),
+            "/tmp/file",
+        );
+        assert!(matches!(error, Error::OutOfSpace { .. }));
+        assert_eq!(error.path(), Some(Path::new("/tmp/file")));
+
+        // The underlying error is kept.
+        let source = error
+            .source()
+            .and_then(|e| e.downcast_ref::<std::io::Error>())
+            .expect("out of space errors have a source");
+        assert_eq!(source.kind(), std::io::ErrorKind::QuotaExceeded);
+    }
+
+    #[test]
+    fn test_display_path() {
+        let error = Error::from(std::io::Error::other("failed"));
+        assert_eq!(error.to_string(), "failed");
+        assert_eq!(error.path(), None);
+
+        let error = Error::io_at(std::io::Error::other("failed"), "/tmp/file");
+        assert_eq!(error.to_string(), "/tmp/file: failed");
+        assert_eq!(error.path(), Some(Path::new("/tmp/file")));
+    }
+
+    #[test]
+    fn test_source() {
+        use std::error::Error as _;
+
+        let error = Error::io_at(std::io::Error::other("failed"), "/tmp/file");
+        let source = error.source().expect("I/O errors have a source");
+        assert_eq!(source.to_string(), "failed");
+        assert!(source.downcast_ref::<std::io::Error>().is_some());
+
+        assert!(Error::InvalidFile.source().is_none());
+        assert!(Error::FileVanished(PathBuf::new()).source().is_none());
     }
 }
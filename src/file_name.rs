@@ -0,0 +1,29 @@
+use std::ffi::OsStr;
+use std::path::{Component, Path};
+
+/// Determines whether the name is a plain file name, i.e. whether it is not empty,
+/// not `.` or `..` and does not contain any path separators.
+pub(crate) fn is_valid_file_name(name: &OsStr) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(component)), None) if component == name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_file_name() {
+        assert!(is_valid_file_name(OsStr::new("file.txt")));
+        assert!(is_valid_file_name(OsStr::new(".hidden")));
+        assert!(!is_valid_file_name(OsStr::new("")));
+        assert!(!is_valid_file_name(OsStr::new(".")));
+        assert!(!is_valid_file_name(OsStr::new("..")));
+        assert!(!is_valid_file_name(OsStr::new("nested/file")));
+        assert!(!is_valid_file_name(OsStr::new("file/")));
+        assert!(!is_valid_file_name(OsStr::new("/file")));
+    }
+}
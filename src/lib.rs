@@ -24,25 +24,117 @@
 //!
 //! ## Features
 //!
+//! * `tokio` - (Default) Enables the [`tokio`](https://crates.io/crates/tokio)-based `TempFile`,
+//!   `TempDir` and related types at the crate root. Disable the default features to use only the
+//!   `async_std` or `blocking` modules without depending on tokio.
 //! * `uuid` - (Default) Enables random file name generation based on the [`uuid`](https://crates.io/crates/uuid) crate.
-//!            Provides the `new` and `new_in`, as well as the `new_with_uuid*` group of methods.
+//!   Provides the `new` and `new_in`, as well as the `new_with_uuid*` group of methods.
+//! * `windows-ads` - Enables the Windows-only `TempFile::with_alternate_stream_cleanup` method
+//!   to remove alternate data streams attached to a file on drop.
+//! * `cap-std` - Enables creating temporary files relative to a [`cap-std`](https://crates.io/crates/cap-std)
+//!   directory capability via `TempFile::new_in_cap_dir`.
+//! * `mime` - Enables naming files after the conventional extension of a MIME type via
+//!   `TempFile::new_for_mime` and `TempFile::new_for_mime_in`, based on the
+//!   [`mime_guess`](https://crates.io/crates/mime_guess) crate.
+//! * `getrandom` - Enables the `SecureNameGenerator`, generating names from the operating system's
+//!   secure random number generator via the [`getrandom`](https://crates.io/crates/getrandom) crate.
+//! * `blocking` - Enables the `blocking` module, providing a synchronous `TempFile` based on
+//!   `std::fs` for code that does not run on an async runtime.
+//! * `async-std` - Enables the `async_std` module, providing a `TempFile` and `TempDir` based on
+//!   [`async-std`](https://crates.io/crates/async-std) instead of tokio.
+//! * `mmap` - Enables mapping files into memory via `TempFile::mmap` and `TempFile::mmap_mut`,
+//!   based on the [`memmap2`](https://crates.io/crates/memmap2) crate.
+//! * `xdg-runtime-dir` - Prefers the per-user runtime directory named by `XDG_RUNTIME_DIR` over
+//!   the shared temporary directory as the default location on Linux.
+//! * `serde` - Implements `Serialize` for `TempFile` and `TempDir`, emitting their path, via
+//!   the [`serde`](https://crates.io/crates/serde) crate.
+//! * `tracing` - Emits [`tracing`](https://crates.io/crates/tracing) events when temporary files
+//!   and directories are created and deleted, warning if they could not be deleted on drop.
+//! * `rt-multi-thread` - Enables tokio's multi-threaded runtime support, allowing blocking work on
+//!   drop to hand the other tasks of a worker thread off via `tokio::task::block_in_place`, and
+//!   `TempFile::set_flush_on_drop` to wait for writes in flight.
 
 // Document crate features on docs.rs.
 #![cfg_attr(docsrs, feature(doc_cfg))]
 // Required for dropping the file.
 #![allow(unsafe_code)]
 
+#[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+#[cfg(feature = "async-std")]
+pub mod async_std;
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "tokio")]
+mod builder;
+mod cleanup_hook;
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "blocking"))]
+mod collision;
+#[cfg(feature = "tokio")]
+mod counting;
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "blocking"))]
+mod creation;
+#[cfg(feature = "tokio")]
+mod creation_info;
 mod errors;
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "blocking"))]
+mod file_name;
+mod location;
+mod name_generator;
+#[cfg(feature = "tokio")]
+mod offload;
+#[cfg(feature = "tokio")]
+mod open_limit;
+#[cfg(feature = "tokio")]
+mod process_dir;
 mod random_name;
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "blocking"))]
+mod removal;
+#[cfg(all(feature = "tokio", feature = "serde"))]
+mod serialization;
+#[cfg(feature = "tokio")]
+mod shared_path;
+#[cfg(feature = "tokio")]
+mod spooled;
+#[cfg(feature = "tokio")]
 mod tempdir;
+#[cfg(feature = "tokio")]
 mod tempfile;
+#[cfg(feature = "tokio")]
+mod temppath;
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "blocking"))]
+mod trace;
 
-pub use errors::Error;
-#[cfg(not(feature = "uuid"))]
-pub(crate) use random_name::RandomName;
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[cfg(feature = "tokio")]
+pub use builder::TempFileBuilder;
+pub use cleanup_hook::on_cleanup_error;
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[cfg(feature = "tokio")]
+pub use counting::CountingTempFile;
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[cfg(feature = "tokio")]
+pub use creation_info::CreationInfo;
+pub use errors::{Error, ErrorKind};
+pub use location::{default_dir, set_default_dir};
+#[cfg(feature = "getrandom")]
+pub use name_generator::SecureNameGenerator;
+pub use name_generator::{NameGenerator, RandomNameGenerator};
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[cfg(feature = "tokio")]
+pub use spooled::SpooledTempFile;
 use std::fmt::Debug;
+#[cfg(feature = "tokio")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[cfg(feature = "tokio")]
 pub use tempdir::TempDir;
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[cfg(feature = "tokio")]
 pub use tempfile::TempFile;
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[cfg(feature = "tokio")]
+pub use temppath::TempPath;
 
 /// Determines the ownership of a temporary file or directory.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -54,3 +146,37 @@ pub enum Ownership {
     /// when the last reference to it is dropped.
     Borrowed,
 }
+
+/// An [`Ownership`] that can be changed through a shared reference,
+/// e.g. when held by a core shared between multiple handles.
+#[cfg(feature = "tokio")]
+pub(crate) struct SharedOwnership(AtomicBool);
+
+#[cfg(feature = "tokio")]
+impl SharedOwnership {
+    pub fn new(ownership: Ownership) -> Self {
+        Self(AtomicBool::new(ownership == Ownership::Owned))
+    }
+
+    pub fn get(&self) -> Ownership {
+        if self.0.load(Ordering::SeqCst) {
+            Ownership::Owned
+        } else {
+            Ownership::Borrowed
+        }
+    }
+
+    pub fn set(&self, ownership: Ownership) {
+        self.0
+            .store(ownership == Ownership::Owned, Ordering::SeqCst);
+    }
+
+    /// Gives up ownership, returning the previous ownership.
+    pub fn release(&self) -> Ownership {
+        if self.0.swap(false, Ordering::SeqCst) {
+            Ownership::Owned
+        } else {
+            Ownership::Borrowed
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// The directory replacing the temporary directory of the system as the default location.
+static DEFAULT_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Sets the directory in which temporary files and directories are created by default,
+/// or restores the temporary directory of the system as the default location.
+///
+/// This applies to all constructors not taking an explicit directory, e.g. [`TempFile::new`]
+/// and [`TempDir::new`], as well as to the per-process subdirectory enabled via
+/// [`TempFile::use_process_subdir`]. This is useful e.g. in containers, to place all
+/// temporary data on a specific mount without passing its path to every call.
+///
+/// The setting is global to the process and may be changed from any thread at any time.
+/// It only affects files and directories created afterwards; existing ones are not moved.
+/// The directory is not checked or created here; constructors fail if it does not exist.
+///
+/// ## Arguments
+///
+/// * `dir` - The directory to use, or `None` to use [`std::env::temp_dir`] again.
+///
+/// ## Example
+///
+/// ```
+/// # use async_tempfile::TempFile;
+/// # let _ = tokio_test::block_on(async {
+/// let scratch = std::env::temp_dir();
+/// async_tempfile::set_default_dir(Some(scratch.clone()));
+/// assert_eq!(async_tempfile::default_dir(), scratch);
+///
+/// let file = TempFile::new().await?;
+/// assert_eq!(file.file_path().parent(), Some(scratch.as_path()));
+/// # async_tempfile::set_default_dir(None);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+///
+/// [`TempFile::new`]: crate::TempFile::new
+/// [`TempDir::new`]: crate::TempDir::new
+/// [`TempFile::use_process_subdir`]: crate::TempFile::use_process_subdir
+pub fn set_default_dir(dir: Option<PathBuf>) {
+    *DEFAULT_DIR
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = dir;
+}
+
+/// Returns the directory in which temporary files and directories are created by default.
+///
+/// This is the directory set via [`set_default_dir`], if any, and [`std::env::temp_dir`]
+/// otherwise. It is resolved on every call, so that changes to the setting or to the
+/// environment at runtime (e.g. of `TMPDIR` on Unix) are honored.
+///
+/// With the `xdg-runtime-dir` feature enabled on Linux, the per-user runtime directory named
+/// by the `XDG_RUNTIME_DIR` environment variable is preferred over [`std::env::temp_dir`] if it
+/// is set to an absolute path of an existing directory. Unlike the shared `/tmp`, this directory
+/// is only accessible by the current user and usually backed by memory, so it is well suited for
+/// sensitive data, though often limited in size.
+pub fn default_dir() -> PathBuf {
+    let configured = DEFAULT_DIR
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+
+    #[cfg(all(target_os = "linux", feature = "xdg-runtime-dir"))]
+    let configured = configured.or_else(runtime_dir);
+
+    configured.unwrap_or_else(std::env::temp_dir)
+}
+
+/// Returns the per-user runtime directory, if it is set and exists.
+#[cfg(all(target_os = "linux", feature = "xdg-runtime-dir"))]
+fn runtime_dir() -> Option<PathBuf> {
+    let dir = PathBuf::from(std::env::var_os("XDG_RUNTIME_DIR")?);
+
+    // The specification requires relative paths to be ignored.
+    if dir.is_absolute() && dir.is_dir() {
+        Some(dir)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,88 @@
+use crate::random_name::RandomName;
+
+/// Generates file and directory names for temporary files and directories.
+///
+/// Implementations are used via [`TempFile::new_with_generator`](crate::TempFile::new_with_generator)
+/// and [`TempDir::new_with_generator`](crate::TempDir::new_with_generator), e.g. to satisfy
+/// requirements on the randomness of names.
+///
+/// ## Example
+///
+/// ```
+/// # use async_tempfile::{NameGenerator, TempFile};
+/// struct Sequential;
+///
+/// impl NameGenerator for Sequential {
+///     fn generate(&self, prefix: &str) -> String {
+///         format!("{prefix}{}", std::process::id())
+///     }
+/// }
+///
+/// # let _ = tokio_test::block_on(async {
+/// let file = TempFile::new_with_generator(&Sequential, std::env::temp_dir()).await?;
+/// assert!(file.file_path().file_name().unwrap().to_str().unwrap().starts_with("atmp_"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub trait NameGenerator {
+    /// Generates a new name starting with the specified prefix.
+    ///
+    /// The result must be a plain file name, i.e. it must not contain path separators.
+    fn generate(&self, prefix: &str) -> String;
+}
+
+/// Generates names from the process ID, a memory address and the current time.
+///
+/// This is the scheme used by [`TempFile::new`](crate::TempFile::new) if the `uuid` feature
+/// is disabled. The names are unpredictable to a limited degree only; use
+/// `SecureNameGenerator` (`getrandom` feature) if names must not be guessable.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomNameGenerator;
+
+impl NameGenerator for RandomNameGenerator {
+    fn generate(&self, prefix: &str) -> String {
+        RandomName::new(prefix).as_str().to_owned()
+    }
+}
+
+/// Generates names from 128 bits obtained from the operating system's cryptographically
+/// secure random number generator.
+///
+/// ## Panics
+///
+/// Panics if the operating system fails to provide random data.
+#[cfg(feature = "getrandom")]
+#[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SecureNameGenerator;
+
+#[cfg(feature = "getrandom")]
+impl NameGenerator for SecureNameGenerator {
+    fn generate(&self, prefix: &str) -> String {
+        let mut bytes = [0u8; 16];
+        getrandom::fill(&mut bytes).expect("failed to obtain random data from the system");
+        let random = bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+        format!("{prefix}{random}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_name_generator() {
+        let name = RandomNameGenerator.generate("test");
+        assert!(name.starts_with("test"));
+        assert_ne!(name, RandomNameGenerator.generate("test"));
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn test_secure_name_generator() {
+        let name = SecureNameGenerator.generate("test");
+        assert_eq!(name.len(), 4 + 32);
+        assert!(name.starts_with("test"));
+        assert_ne!(name, SecureNameGenerator.generate("test"));
+    }
+}
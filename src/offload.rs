@@ -0,0 +1,42 @@
+#[cfg(feature = "rt-multi-thread")]
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// Runs a blocking file system operation from a synchronous context, such as `Drop`.
+///
+/// On a worker thread of a multi-threaded tokio runtime, the worker hands its other tasks
+/// off to another thread while the operation runs, so that the executor is not stalled.
+/// This requires the `rt-multi-thread` feature. Elsewhere, including on current-thread runtimes
+/// where this is not possible, the operation runs directly on the calling thread.
+///
+/// The operation always completes before this function returns; it is not moved to
+/// a background task, as such tasks may be cancelled when the runtime shuts down.
+pub(crate) fn run_blocking<T>(operation: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "rt-multi-thread")]
+    if let Ok(handle) = Handle::try_current() {
+        if handle.runtime_flavor() == RuntimeFlavor::MultiThread {
+            return tokio::task::block_in_place(operation);
+        }
+    }
+
+    operation()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outside_runtime() {
+        assert_eq!(run_blocking(|| 42), 42);
+    }
+
+    #[tokio::test]
+    async fn test_current_thread_runtime() {
+        assert_eq!(run_blocking(|| 42), 42);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_multi_thread_runtime() {
+        assert_eq!(run_blocking(|| 42), 42);
+    }
+}
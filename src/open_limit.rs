@@ -0,0 +1,32 @@
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// The semaphore limiting the number of concurrently open files, if any.
+static LIMIT: Mutex<Option<Arc<Semaphore>>> = Mutex::new(None);
+
+/// Sets the maximum number of concurrently open files, or removes the limit.
+///
+/// Files and operations that already hold or await a permit of a previous limit are not affected.
+pub(crate) fn set_max_concurrent_opens(max: Option<usize>) {
+    let semaphore = max.map(|max| Arc::new(Semaphore::new(max.clamp(1, Semaphore::MAX_PERMITS))));
+    *LIMIT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = semaphore;
+}
+
+/// A permit to keep a file open, if the number of concurrently open files is limited.
+pub(crate) type Permit = Option<OwnedSemaphorePermit>;
+
+/// Waits for a permit to open a file, if the number of concurrently open files is limited.
+///
+/// The permit is released when dropped, including when the open operation fails. To count
+/// the file as open until it is closed, the permit must be kept along with the file.
+pub(crate) async fn acquire() -> Permit {
+    let semaphore = LIMIT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()?;
+
+    // The semaphore is never closed.
+    semaphore.acquire_owned().await.ok()
+}
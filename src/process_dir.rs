@@ -0,0 +1,73 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+/// The prefix of the per-process subdirectory, followed by the process ID.
+const PROCESS_DIR_PREFIX: &str = "atmpd_";
+
+/// Whether files in the default location are placed in the per-process subdirectory.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The current incarnation of the per-process subdirectory, if any.
+/// The lock also serializes the creation and removal of the directory.
+static CURRENT: Mutex<Weak<ProcessDir>> = Mutex::new(Weak::new());
+
+/// Enables or disables the per-process subdirectory.
+pub(crate) fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Determines whether the per-process subdirectory is enabled.
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// A subdirectory of the default temporary directory, shared by all files of this process
+/// created in the default location.
+///
+/// The directory is created lazily when the first file needs it and removed when the last
+/// file referencing it is dropped, provided it is empty by then.
+pub(crate) struct ProcessDir {
+    path: PathBuf,
+}
+
+impl ProcessDir {
+    /// Returns the per-process subdirectory, creating it if needed.
+    pub fn acquire() -> std::io::Result<Arc<Self>> {
+        let mut current = lock_current();
+        if let Some(dir) = current.upgrade() {
+            return Ok(dir);
+        }
+
+        let name = format!("{}{}", PROCESS_DIR_PREFIX, std::process::id());
+        let path = crate::default_dir().join(name);
+        std::fs::create_dir_all(&path)?;
+
+        let dir = Arc::new(Self { path });
+        *current = Arc::downgrade(&dir);
+        Ok(dir)
+    }
+
+    /// Returns the path of the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ProcessDir {
+    fn drop(&mut self) {
+        // If a new incarnation was created while this one was being dropped, it uses the
+        // same path; removing the directory would pull it from under the new files.
+        let current = lock_current();
+        if current.strong_count() == 0 {
+            // Only removes the directory if it is empty, e.g. keeping borrowed files.
+            let _ = std::fs::remove_dir(&self.path);
+        }
+    }
+}
+
+fn lock_current() -> MutexGuard<'static, Weak<ProcessDir>> {
+    CURRENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
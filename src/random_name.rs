@@ -1,4 +1,20 @@
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// The number of names generated so far, guaranteeing unique names within the process.
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A source for the current time, expressed as the duration since the Unix epoch.
+///
+/// The default is [`system_clock`]; tests may inject a deterministic clock
+/// via `RandomName::with_clock` to reproduce otherwise random names.
+pub(crate) type Clock = fn() -> Duration;
+
+/// A source for the sequence number distinguishing names generated within the process.
+///
+/// The default is [`next_count`]; tests may inject a fixed counter along with a fixed clock
+/// via `RandomName::with_sources` to generate identical names, e.g. to force collisions.
+pub(crate) type Counter = fn() -> u64;
 
 /// Represents a randomly generated file name.
 pub(crate) struct RandomName {
@@ -6,21 +22,44 @@ pub(crate) struct RandomName {
 }
 
 impl RandomName {
-    #[allow(dead_code)]
     pub fn new(prefix: &str) -> Self {
+        Self::generate(prefix, system_clock, next_count)
+    }
+
+    /// Generates a new name using the specified clock as a source of variation.
+    #[cfg(test)]
+    pub fn with_clock(prefix: &str, clock: Clock) -> Self {
+        Self::generate(prefix, clock, next_count)
+    }
+
+    /// Generates a new name using the specified clock and counter as sources of variation.
+    ///
+    /// The name is fully determined by the sources within a process, so that fixed sources
+    /// yield identical names.
+    #[cfg(test)]
+    pub fn with_sources(prefix: &str, clock: Clock, counter: Counter) -> Self {
+        Self::generate(prefix, clock, counter)
+    }
+
+    fn generate(prefix: &str, clock: Clock, counter: Counter) -> Self {
         let pid = std::process::id();
 
-        // Using the address of a local variable for extra variation.
-        let marker = &pid as *const _ as usize;
+        // Using the address of a static for extra variation between processes.
+        let marker = &COUNTER as *const _ as usize;
 
         // Current timestamp for added variation.
-        let now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap_or(std::time::Duration::from_secs(0));
+        let now = clock();
         let (secs, subsec_nanos) = (now.as_secs(), now.subsec_nanos());
 
+        // The counter makes names unique regardless of the clock resolution. It is separated
+        // from the other parts so that different counter values never yield the same name.
+        let count = counter();
+
         Self {
-            name: format!("{}{}{:x}{:x}{:x}", prefix, pid, marker, secs, subsec_nanos),
+            name: format!(
+                "{}{}{:x}{:x}{:x}_{:x}",
+                prefix, pid, marker, secs, subsec_nanos, count
+            ),
         }
     }
 
@@ -35,6 +74,18 @@ impl AsRef<str> for RandomName {
     }
 }
 
+/// Returns the next value of the process-wide counter.
+fn next_count() -> u64 {
+    COUNTER.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Returns the current system time, or the Unix epoch if the clock is set before it.
+fn system_clock() -> Duration {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::from_secs(0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,4 +98,50 @@ mod tests {
         assert!(second.as_str().starts_with("test"));
         assert_ne!(first.as_str(), second.as_str());
     }
+
+    #[test]
+    fn test_frozen_clock() {
+        fn frozen() -> Duration {
+            Duration::new(0x1234, 0x5678)
+        }
+
+        let first = RandomName::with_clock("test", frozen);
+        let second = RandomName::with_clock("test", frozen);
+        assert!(first.as_str().starts_with("test"));
+        assert!(first.as_str().contains("12345678_"));
+        assert!(second.as_str().contains("12345678_"));
+
+        // The counter keeps names unique even if the clock does not advance.
+        assert_ne!(first.as_str(), second.as_str());
+    }
+
+    #[test]
+    fn test_fixed_sources() {
+        fn frozen() -> Duration {
+            Duration::new(0x1234, 0x5678)
+        }
+
+        fn fixed() -> u64 {
+            0x42
+        }
+
+        // Fixed sources yield identical names, as needed to exercise collisions.
+        let first = RandomName::with_sources("test", frozen, fixed);
+        let second = RandomName::with_sources("test", frozen, fixed);
+        assert_eq!(first.as_str(), second.as_str());
+        assert!(first.as_str().ends_with("12345678_42"));
+    }
+
+    #[test]
+    fn test_ticking_clock() {
+        static TICKS: AtomicU64 = AtomicU64::new(1);
+
+        fn ticking() -> Duration {
+            Duration::from_secs(TICKS.fetch_add(1, Ordering::SeqCst))
+        }
+
+        let first = RandomName::with_clock("test", ticking);
+        let second = RandomName::with_clock("test", ticking);
+        assert_ne!(first.as_str(), second.as_str());
+    }
 }
@@ -0,0 +1,254 @@
+//! Removal of temporary files and directories, shared by the types of all runtimes.
+
+use crate::cleanup_hook;
+use crate::trace;
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+use crate::Error;
+use std::io::ErrorKind;
+use std::path::Path;
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+use std::path::PathBuf;
+
+/// Deletes the file at `path`, tracing the deletion and reporting failures other than the
+/// file being gone already to the cleanup hook.
+#[cfg(any(feature = "async-std", feature = "blocking"))]
+pub(crate) fn remove_file(path: &Path) {
+    let result = std::fs::remove_file(path);
+    report_file_removal(path, &result);
+}
+
+/// Traces the deletion of the file at `path` and reports failures other than the file being
+/// gone already to the cleanup hook.
+pub(crate) fn report_file_removal(path: &Path, result: &std::io::Result<()>) {
+    trace::file_deleted(path, result);
+    match result {
+        Err(error) if error.kind() != ErrorKind::NotFound => cleanup_hook::report(path, error),
+        _ => {}
+    }
+}
+
+/// Removes the directory at `path` and all of its contents, continuing past entries that
+/// cannot be removed. The deletion is traced and failures are reported to the cleanup hook.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+pub(crate) fn remove_dir(path: &Path) {
+    let failed = remove_dir_all(path);
+    trace::dir_deleted(path, &failed);
+    for (path, error) in &failed {
+        cleanup_hook::report(path, error);
+    }
+}
+
+/// Converts the failures reported by [`remove_dir_all`] into an [`Error::RemovalFailed`],
+/// keeping the error of the first path that could not be removed.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+pub(crate) fn into_result(failed: Vec<(PathBuf, std::io::Error)>) -> Result<(), Error> {
+    let mut failed = failed.into_iter();
+    let Some((path, source)) = failed.next() else {
+        return Ok(());
+    };
+    let paths = std::iter::once(path)
+        .chain(failed.map(|(path, _)| path))
+        .collect();
+    Err(Error::RemovalFailed { paths, source })
+}
+
+/// Removes the directory and all of its contents on a best-effort basis,
+/// continuing past entries that cannot be removed.
+///
+/// Returns the paths that could not be removed along with the errors encountered. Directories
+/// that could not be removed only because they still contain such paths are not reported.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+pub(crate) fn remove_dir_all(root: &Path) -> Vec<(PathBuf, std::io::Error)> {
+    // Fast path for the common case.
+    match std::fs::remove_dir_all(root) {
+        Ok(()) => return Vec::new(),
+        Err(e) if e.kind() == ErrorKind::NotFound => return Vec::new(),
+        Err(_) => {}
+    }
+
+    let mut failed = Vec::new();
+
+    // Directories are visited twice: first to remove their non-directory entries and to
+    // schedule their subdirectories, then to remove the directory itself once all
+    // subdirectories were processed. An explicit stack avoids deep recursion.
+    let mut stack = vec![(root.to_path_buf(), false)];
+    while let Some((dir, emptied)) = stack.pop() {
+        if emptied {
+            match remove_with_retry(root, &dir, |path| std::fs::remove_dir(path)) {
+                Err(e) if e.kind() != ErrorKind::DirectoryNotEmpty => failed.push((dir, e)),
+                _ => {}
+            }
+            continue;
+        }
+
+        let entries = match read_dir_with_retry(root, &dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => {
+                failed.push((dir, e));
+                continue;
+            }
+        };
+
+        stack.push((dir, true));
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            // Symbolic links are removed rather than followed.
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                stack.push((path, false));
+            } else if let Err(e) = remove_with_retry(root, &path, |path| std::fs::remove_file(path))
+            {
+                failed.push((path, e));
+            }
+        }
+    }
+
+    failed
+}
+
+/// Removes the path inside the tree at `root`, retrying once after attempting to fix
+/// the permissions.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+fn remove_with_retry(
+    root: &Path,
+    path: &Path,
+    remove: fn(&Path) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let result = match remove(path) {
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            allow_removal(root, path);
+            remove(path)
+        }
+        result => result,
+    };
+
+    match result {
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Reads the directory inside the tree at `root`, retrying once after attempting to make
+/// it accessible.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+fn read_dir_with_retry(root: &Path, dir: &Path) -> std::io::Result<std::fs::ReadDir> {
+    match std::fs::read_dir(dir) {
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            allow_removal(root, dir);
+            make_accessible(dir);
+            std::fs::read_dir(dir)
+        }
+        result => result,
+    }
+}
+
+/// Attempts to grant the permissions required to remove the path inside the tree at `root`.
+///
+/// On Unix, this requires write and search permissions on the parent directory. The parent
+/// of `root` is not part of the tree, so its permissions are never changed.
+#[cfg(all(unix, any(feature = "tokio", feature = "async-std")))]
+fn allow_removal(root: &Path, path: &Path) {
+    if let Some(parent) = path.parent() {
+        if Some(parent) != root.parent() {
+            make_accessible(parent);
+        }
+    }
+}
+
+/// Attempts to grant the permissions required to remove the path.
+///
+/// On Windows, read-only files cannot be removed.
+#[cfg(all(not(unix), any(feature = "tokio", feature = "async-std")))]
+fn allow_removal(_root: &Path, path: &Path) {
+    make_accessible(path);
+}
+
+/// Attempts to grant the owner full access to the directory.
+#[cfg(all(unix, any(feature = "tokio", feature = "async-std")))]
+fn make_accessible(dir: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    if let Ok(metadata) = std::fs::metadata(dir) {
+        let mut permissions = metadata.permissions();
+        permissions.set_mode(permissions.mode() | 0o700);
+        let _ = std::fs::set_permissions(dir, permissions);
+    }
+}
+
+/// Attempts to clear the read-only attribute of the path.
+#[cfg(all(not(unix), any(feature = "tokio", feature = "async-std")))]
+#[allow(clippy::permissions_set_readonly_false)]
+fn make_accessible(path: &Path) {
+    if let Ok(metadata) = std::fs::metadata(path) {
+        let mut permissions = metadata.permissions();
+        permissions.set_readonly(false);
+        let _ = std::fs::set_permissions(path, permissions);
+    }
+}
+
+#[cfg(all(test, unix, any(feature = "tokio", feature = "async-std")))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_remove_restricted_tree() {
+        let root = std::env::temp_dir().join(format!("atmpd_removal_{}", std::process::id()));
+        let locked = root.join("locked");
+        std::fs::create_dir_all(locked.join("nested")).unwrap();
+        std::fs::write(locked.join("file"), b"content").unwrap();
+        std::fs::write(locked.join("nested").join("file"), b"content").unwrap();
+
+        // Without write and search permissions, the entries cannot be removed.
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o400)).unwrap();
+
+        assert!(remove_dir_all(&root).is_empty());
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_permissions_outside_tree_are_kept() {
+        let parent =
+            std::env::temp_dir().join(format!("atmpd_removal_parent_{}", std::process::id()));
+        let root = parent.join("root");
+        std::fs::create_dir_all(root.join("nested")).unwrap();
+        std::fs::set_permissions(&parent, std::fs::Permissions::from_mode(0o500)).unwrap();
+
+        allow_removal(&root, &root);
+        let mode = std::fs::metadata(&parent).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o500);
+
+        // Entries inside the tree may be fixed.
+        std::fs::set_permissions(&root, std::fs::Permissions::from_mode(0o500)).unwrap();
+        allow_removal(&root, &root.join("nested"));
+        let mode = std::fs::metadata(&root).unwrap().permissions().mode();
+        assert_eq!(mode & 0o700, 0o700);
+
+        std::fs::set_permissions(&parent, std::fs::Permissions::from_mode(0o700)).unwrap();
+        std::fs::remove_dir_all(&parent).unwrap();
+    }
+
+    #[test]
+    fn test_into_result_keeps_first_error() {
+        assert!(into_result(Vec::new()).is_ok());
+
+        let failed = vec![
+            (
+                PathBuf::from("a"),
+                std::io::Error::from(ErrorKind::PermissionDenied),
+            ),
+            (PathBuf::from("b"), std::io::Error::from(ErrorKind::Other)),
+        ];
+        let error = into_result(failed).unwrap_err();
+        assert_eq!(error.path(), Some(Path::new("a")));
+        let source = std::error::Error::source(&error).unwrap();
+        let source = source.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(source.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn test_remove_missing_dir() {
+        let root = std::env::temp_dir().join("atmpd_removal_missing");
+        assert!(remove_dir_all(&root).is_empty());
+    }
+}
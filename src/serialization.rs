@@ -0,0 +1,44 @@
+use crate::{TempDir, TempFile};
+use serde::{Serialize, Serializer};
+
+/// Serializes the path of the file, e.g. to reference it from a task descriptor.
+///
+/// Deserializing a `TempFile` is not supported, as wrapping the file requires an
+/// asynchronous call. Deserialize the path as a [`PathBuf`](std::path::PathBuf) instead
+/// and wrap it via [`TempFile::from_existing`], usually with [`Ownership::Borrowed`]
+/// so that a file owned by someone else is not deleted.
+///
+/// [`Ownership::Borrowed`]: crate::Ownership::Borrowed
+///
+/// ## Example
+///
+/// ```
+/// # use async_tempfile::{Ownership, TempFile};
+/// # use std::path::PathBuf;
+/// # let _ = tokio_test::block_on(async {
+/// let file = TempFile::new().await?;
+/// let json = serde_json::to_string(&file)?;
+///
+/// let path: PathBuf = serde_json::from_str(&json)?;
+/// let borrowed = TempFile::from_existing(path, Ownership::Borrowed).await?;
+/// assert_eq!(borrowed.file_path(), file.file_path());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for TempFile {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.file_path().serialize(serializer)
+    }
+}
+
+/// Serializes the path of the directory.
+///
+/// As with [`TempFile`], deserialize the path as a [`PathBuf`](std::path::PathBuf) and
+/// wrap it via [`TempDir::from_existing`] instead.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for TempDir {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.dir_path().serialize(serializer)
+    }
+}
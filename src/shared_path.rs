@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A path that is shared by all references to a file and can be changed at runtime,
+/// e.g. when renaming the file.
+///
+/// References returned by [`SharedPath::get`] remain valid after the path was changed:
+/// every path ever set is kept alive until the instance itself is dropped. Since paths
+/// are expected to change rarely, the additional memory is negligible.
+pub(crate) struct SharedPath {
+    /// The current path, pointing into one of the entries of `history`.
+    current: AtomicPtr<PathBuf>,
+
+    /// All paths set so far, including the current one.
+    history: Mutex<Vec<Arc<PathBuf>>>,
+}
+
+impl SharedPath {
+    pub fn new(path: PathBuf) -> Self {
+        let path = Arc::new(path);
+        Self {
+            current: AtomicPtr::new(Arc::as_ptr(&path).cast_mut()),
+            history: Mutex::new(vec![path]),
+        }
+    }
+
+    /// Returns the current path.
+    pub fn get(&self) -> &PathBuf {
+        // SAFETY: The pointer refers to an entry in `history`, which is only ever appended to
+        // and lives as long as `self`. The pointee is never mutated.
+        unsafe { &*self.current.load(Ordering::SeqCst) }
+    }
+
+    /// Replaces the current path.
+    pub fn set(&self, path: PathBuf) {
+        let path = Arc::new(path);
+        let mut history = self
+            .history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.current
+            .store(Arc::as_ptr(&path).cast_mut(), Ordering::SeqCst);
+        history.push(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_references_outlive_changes() {
+        let path = SharedPath::new(PathBuf::from("first"));
+        let first = path.get();
+
+        path.set(PathBuf::from("second"));
+        assert_eq!(first, &PathBuf::from("first"));
+        assert_eq!(path.get(), &PathBuf::from("second"));
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{Error, TempFile};
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::io::{Cursor, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+/// The pending creation of the file when spilling to disk. On failure, the buffer is handed back.
+type SpillFuture =
+    Pin<Box<dyn Future<Output = Result<TempFile, (std::io::Error, Cursor<Vec<u8>>)>> + Send>>;
+
+/// A temporary file that keeps its contents in memory until they exceed a size threshold,
+/// at which point they are transparently moved to a [`TempFile`] on disk.
+///
+/// This avoids disk I/O for small payloads, similar to Python's `SpooledTemporaryFile`.
+/// The file is spilled when a write would extend the contents beyond the threshold, or
+/// explicitly via [`SpooledTempFile::spill`]; it never moves back into memory. Reads, writes
+/// and seeks behave the same in either state, and the position is kept when spilling.
+///
+/// ## Example
+///
+/// ```
+/// # use async_tempfile::SpooledTempFile;
+/// # use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+/// # let _ = tokio_test::block_on(async {
+/// let mut file = SpooledTempFile::new(8);
+/// file.write_all(b"small").await?;
+/// assert!(!file.is_spilled());
+///
+/// file.write_all(b" and larger").await?;
+/// assert!(file.is_spilled());
+///
+/// file.rewind().await?;
+/// let mut content = String::new();
+/// file.read_to_string(&mut content).await?;
+/// assert_eq!(content, "small and larger");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct SpooledTempFile {
+    /// The maximum number of bytes kept in memory.
+    max_in_memory: usize,
+
+    /// The directory to create the file in when spilling, or the default location if `None`.
+    dir: Option<PathBuf>,
+
+    /// The storage of the contents.
+    state: State,
+
+    /// A seek requested while spilling, applied to the file once it was created.
+    pending_seek: Option<SeekFrom>,
+}
+
+enum State {
+    /// The contents are kept in memory.
+    InMemory(Cursor<Vec<u8>>),
+    /// The contents are being moved to a file.
+    Spilling(SpillFuture),
+    /// The contents are stored in a file.
+    OnDisk(TempFile),
+}
+
+impl SpooledTempFile {
+    /// Creates a new spooled temporary file that keeps up to `max_in_memory` bytes in memory
+    /// before spilling to a file in the default location.
+    ///
+    /// No file is created until the contents exceed the threshold.
+    ///
+    /// ## Arguments
+    ///
+    /// * `max_in_memory` - The maximum number of bytes to keep in memory.
+    pub fn new(max_in_memory: usize) -> Self {
+        Self {
+            max_in_memory,
+            dir: None,
+            state: State::InMemory(Cursor::default()),
+            pending_seek: None,
+        }
+    }
+
+    /// Creates a new spooled temporary file that keeps up to `max_in_memory` bytes in memory
+    /// before spilling to a file in the specified directory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `max_in_memory` - The maximum number of bytes to keep in memory.
+    /// * `dir` - The directory to create the file in when spilling.
+    pub fn new_in<P: AsRef<Path>>(max_in_memory: usize, dir: P) -> Self {
+        Self {
+            dir: Some(dir.as_ref().to_path_buf()),
+            ..Self::new(max_in_memory)
+        }
+    }
+
+    /// Determines whether the contents were moved to a file on disk.
+    pub fn is_spilled(&self) -> bool {
+        !matches!(self.state, State::InMemory(_))
+    }
+
+    /// Moves the contents to a file on disk, if they are still kept in memory.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the file could not be created or written. The contents are then
+    /// kept in memory, and spilling is attempted again on the next write exceeding the threshold.
+    pub async fn spill(&mut self) -> Result<(), Error> {
+        Ok(std::future::poll_fn(|cx| self.poll_spill(cx)).await?)
+    }
+
+    /// Returns the file the contents were moved to, if spilled.
+    pub fn as_file(&self) -> Option<&TempFile> {
+        match &self.state {
+            State::OnDisk(file) => Some(file),
+            _ => None,
+        }
+    }
+
+    /// Drives the spilling to completion, starting it if the contents are still in memory.
+    fn poll_spill(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        loop {
+            match &mut self.state {
+                State::OnDisk(_) => return Poll::Ready(Ok(())),
+                State::InMemory(cursor) => {
+                    let cursor = std::mem::take(cursor);
+                    self.state = State::Spilling(Box::pin(spill(cursor, self.dir.clone())));
+                }
+                State::Spilling(future) => match ready!(future.as_mut().poll(cx)) {
+                    Ok(file) => self.state = State::OnDisk(file),
+                    Err((error, cursor)) => {
+                        self.state = State::InMemory(cursor);
+                        return Poll::Ready(Err(error));
+                    }
+                },
+            }
+        }
+    }
+
+    /// Completes a pending spill and returns the file, or the in-memory buffer otherwise.
+    fn poll_storage(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<Storage<'_>>> {
+        if matches!(self.state, State::Spilling(_)) {
+            ready!(self.poll_spill(cx))?;
+        }
+        Poll::Ready(Ok(match &mut self.state {
+            State::InMemory(cursor) => Storage::InMemory(cursor),
+            State::OnDisk(file) => Storage::OnDisk(file),
+            State::Spilling(_) => unreachable!("spilling was completed"),
+        }))
+    }
+}
+
+/// The storage of the contents once no spill is pending.
+enum Storage<'a> {
+    InMemory(&'a mut Cursor<Vec<u8>>),
+    OnDisk(&'a mut TempFile),
+}
+
+/// Creates a file holding the buffered contents, positioned like the buffer.
+async fn spill(
+    cursor: Cursor<Vec<u8>>,
+    dir: Option<PathBuf>,
+) -> Result<TempFile, (std::io::Error, Cursor<Vec<u8>>)> {
+    let result = async {
+        let mut file = match &dir {
+            Some(dir) => TempFile::new_in(dir.as_path()).await,
+            None => TempFile::new().await,
+        }
+        .map_err(into_io_error)?;
+        file.write_all(cursor.get_ref()).await?;
+        file.flush().await?;
+        file.seek(SeekFrom::Start(cursor.position())).await?;
+        Ok(file)
+    }
+    .await;
+    result.map_err(|error| (error, cursor))
+}
+
+/// Converts an error of this crate into an I/O error for the I/O traits.
+fn into_io_error(error: Error) -> std::io::Error {
+    match error {
+        Error::Io { source, .. } | Error::OutOfSpace { source, .. } => source,
+        error => std::io::Error::other(error),
+    }
+}
+
+impl Debug for SpooledTempFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("SpooledTempFile");
+        debug.field("max_in_memory", &self.max_in_memory);
+        match &self.state {
+            State::InMemory(cursor) => debug.field("in_memory", &cursor.get_ref().len()),
+            State::Spilling(_) => debug.field("spilling", &true),
+            State::OnDisk(file) => debug.field("file", file),
+        };
+        debug.finish()
+    }
+}
+
+/// Buffering writes in memory up to the threshold, forwarding them to the file afterwards.
+impl AsyncWrite for SpooledTempFile {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, std::io::Error>> {
+        if let State::InMemory(cursor) = &self.state {
+            let end = cursor.position().saturating_add(buf.len() as u64);
+            if end > self.max_in_memory as u64 {
+                ready!(self.poll_spill(cx))?;
+            }
+        }
+
+        match ready!(self.poll_storage(cx))? {
+            Storage::InMemory(cursor) => Pin::new(cursor).poll_write(cx, buf),
+            Storage::OnDisk(file) => Pin::new(file).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), std::io::Error>> {
+        match ready!(self.poll_storage(cx))? {
+            Storage::InMemory(cursor) => Pin::new(cursor).poll_flush(cx),
+            Storage::OnDisk(file) => Pin::new(file).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), std::io::Error>> {
+        match ready!(self.poll_storage(cx))? {
+            Storage::InMemory(cursor) => Pin::new(cursor).poll_shutdown(cx),
+            Storage::OnDisk(file) => Pin::new(file).poll_shutdown(cx),
+        }
+    }
+}
+
+/// Reading from the memory buffer or the file.
+impl AsyncRead for SpooledTempFile {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match ready!(self.poll_storage(cx))? {
+            Storage::InMemory(cursor) => Pin::new(cursor).poll_read(cx, buf),
+            Storage::OnDisk(file) => Pin::new(file).poll_read(cx, buf),
+        }
+    }
+}
+
+/// Seeking in the memory buffer or the file.
+impl AsyncSeek for SpooledTempFile {
+    fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        match &mut self.state {
+            State::InMemory(cursor) => Pin::new(cursor).start_seek(position),
+            State::OnDisk(file) => Pin::new(file).start_seek(position),
+            State::Spilling(_) => {
+                // The seek is applied once the file was created.
+                self.pending_seek = Some(position);
+                Ok(())
+            }
+        }
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        if self.pending_seek.is_some() {
+            if let Err(error) = ready!(self.poll_spill(cx)) {
+                self.pending_seek = None;
+                return Poll::Ready(Err(error));
+            }
+            if let Some(position) = self.pending_seek.take() {
+                self.as_mut().start_seek(position)?;
+            }
+        }
+
+        match ready!(self.poll_storage(cx))? {
+            Storage::InMemory(cursor) => Pin::new(cursor).poll_complete(cx),
+            Storage::OnDisk(file) => Pin::new(file).poll_complete(cx),
+        }
+    }
+}
@@ -1,19 +1,34 @@
-#[cfg(not(feature = "uuid"))]
-use crate::RandomName;
-use crate::{Error, Ownership};
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS, DIR_PREFIX};
+use crate::creation;
+use crate::errors::check_directory;
+use crate::file_name::is_valid_file_name;
+use crate::offload;
+use crate::removal;
+use crate::trace;
+use crate::{Error, NameGenerator, Ownership, SharedOwnership, TempFile};
 use std::borrow::Borrow;
+use std::ffi::OsStr;
 use std::fmt::{Debug, Formatter};
 use std::mem::ManuallyDrop;
 use std::ops::Deref;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
 #[cfg(feature = "uuid")]
 use uuid::Uuid;
 
-const DIR_PREFIX: &str = "atmpd_";
+mod close;
+mod filesystem;
+mod secure;
+mod walk;
 
 /// A named temporary directory that will be cleaned automatically
 /// after the last reference to it is dropped.
+///
+/// Like for [`TempFile`], owned directories are deleted synchronously when the last
+/// reference is dropped, handing off the other tasks of the worker thread on a multi-threaded
+/// tokio runtime if the `rt-multi-thread` feature is enabled. Use [`TempDir::close`] to delete
+/// a directory asynchronously and observe errors.
 pub struct TempDir {
     /// A local reference to the directory.
     dir: ManuallyDrop<PathBuf>,
@@ -33,13 +48,18 @@ struct TempDirCore {
     /// A hacky approach to allow for "non-owned" files.
     /// If set to `Ownership::Owned`, the file specified in `path` will be deleted
     /// when this instance is dropped. If set to `Ownership::Borrowed`, the file will be kept.
-    ownership: Ownership,
+    /// The ownership is shared by all references to the directory and can be changed at runtime.
+    ownership: SharedOwnership,
 }
 
 impl TempDir {
     /// Creates a new temporary directory in the default location.
     /// When the instance goes out of scope, the directory will be deleted.
     ///
+    /// The default location is resolved via [`default_dir`](crate::default_dir) on every call,
+    /// so changes made via [`set_default_dir`](crate::set_default_dir) or to the environment at
+    /// runtime (e.g. of `TMPDIR` on Unix) apply to all directories created afterwards.
+    ///
     /// ## Example
     ///
     /// ```
@@ -71,6 +91,12 @@ impl TempDir {
     ///
     /// * `name` - The name of the directory to create in the default temporary directory root.
     ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is not a plain directory name, i.e. if it is empty,
+    /// `.` or `..`, or contains path separators, as is the case for absolute paths. The name is
+    /// rejected rather than sanitized, so that it cannot escape the directory.
+    ///
     /// ## Example
     ///
     /// ```
@@ -132,6 +158,10 @@ impl TempDir {
     /// Creates a new temporary directory in the specified location.
     /// When the instance goes out of scope, the directory will be deleted.
     ///
+    /// The directory is created with a random name that must not exist yet, so that an existing
+    /// directory is never taken over and deleted on drop. Should the name exist, a new name is
+    /// generated, trying up to 16 names.
+    ///
     /// ## Crate Features
     ///
     /// * `uuid` - When the `uuid` crate feature is enabled, a random UUIDv4 is used to
@@ -161,18 +191,13 @@ impl TempDir {
     /// assert!(fs::metadata(dir_path).await.is_err());
     /// # Ok::<(), Error>(())
     /// # });
+    /// ```
     pub async fn new_in<P: Borrow<Path>>(root_dir: P) -> Result<Self, Error> {
-        #[cfg(feature = "uuid")]
-        {
-            let id = Uuid::new_v4();
-            Self::new_with_uuid_in(id, root_dir).await
-        }
-
-        #[cfg(not(feature = "uuid"))]
-        {
-            let name = RandomName::new(DIR_PREFIX);
-            Self::new_with_name_in(name, root_dir).await
-        }
+        let root_dir = root_dir.borrow();
+        collision::retry(DEFAULT_ATTEMPTS, || {
+            Self::new_with_name_in_exclusive(random_name(DIR_PREFIX), root_dir)
+        })
+        .await
     }
 
     /// Creates a new temporary directory in the specified location.
@@ -183,6 +208,12 @@ impl TempDir {
     /// * `dir` - The root directory to create the directory in.
     /// * `name` - The directory name to use.
     ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is not a plain directory name, i.e. if it is
+    /// empty, `.` or `..`, or contains path separators, as is the case for absolute paths. The
+    /// name is rejected rather than sanitized, so that it cannot escape the root directory.
+    ///
     /// ## Example
     ///
     /// ```
@@ -209,13 +240,100 @@ impl TempDir {
         root_dir: P,
     ) -> Result<Self, Error> {
         let dir = root_dir.borrow();
-        if !dir.is_dir() {
-            return Err(Error::InvalidDirectory);
-        }
         let file_name = name.as_ref();
+        if !is_valid_file_name(OsStr::new(file_name)) {
+            return Err(Error::InvalidFile);
+        }
+        check_directory(dir).await?;
         let mut path = PathBuf::from(dir);
         path.push(file_name);
-        Self::new_internal(path, Ownership::Owned).await
+        Self::new_internal(path, Ownership::Owned, false).await
+    }
+
+    /// Creates a new temporary directory with the specified name in the specified location,
+    /// failing if a directory with that name already exists.
+    /// When the instance goes out of scope, the directory will be deleted.
+    ///
+    /// Unlike [`TempDir::new_with_name_in`], which creates the directory recursively and takes
+    /// ownership of an existing directory of the same name, this never takes ownership of a
+    /// directory it did not create, so that someone else's data is not deleted on drop. The
+    /// root directory must exist. This is recommended for caller-supplied names.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The directory name to use.
+    /// * `root_dir` - The root directory to create the directory in.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] with [`std::io::ErrorKind::AlreadyExists`] if the directory exists,
+    /// and [`Error::InvalidFile`] if the name is not a plain directory name.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{ErrorKind, TempDir};
+    /// # let _ = tokio_test::block_on(async {
+    /// let root = std::env::temp_dir();
+    /// let dir = TempDir::new_with_name_in_exclusive("exclusive.dir", root.as_path()).await?;
+    ///
+    /// let error = TempDir::new_with_name_in_exclusive("exclusive.dir", root.as_path())
+    ///     .await
+    ///     .unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+    /// # drop(dir);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn new_with_name_in_exclusive<N: AsRef<str>, P: Borrow<Path>>(
+        name: N,
+        root_dir: P,
+    ) -> Result<Self, Error> {
+        let dir = root_dir.borrow();
+        let file_name = name.as_ref();
+        if !is_valid_file_name(OsStr::new(file_name)) {
+            return Err(Error::InvalidFile);
+        }
+        check_directory(dir).await?;
+        Self::new_internal(dir.join(file_name), Ownership::Owned, true).await
+    }
+
+    /// Creates a new temporary directory in the specified location, named by the specified
+    /// generator. When the instance goes out of scope, the directory will be deleted.
+    ///
+    /// The directory is created exclusively; if a directory with the generated name already
+    /// exists, the generator is asked for another name, up to 16 names in total.
+    ///
+    /// ## Arguments
+    ///
+    /// * `generator` - The generator to obtain the directory name from.
+    /// * `root_dir` - The root directory to create the directory in.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the generated name is not a valid directory name, and
+    /// an error of kind [`ErrorKind::AlreadyExists`](crate::ErrorKind::AlreadyExists) if all
+    /// generated names were taken.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{RandomNameGenerator, TempDir};
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new_with_generator(&RandomNameGenerator, std::env::temp_dir()).await?;
+    /// assert!(dir.dir_path().is_dir());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn new_with_generator<G: NameGenerator + ?Sized, P: Borrow<Path>>(
+        generator: &G,
+        root_dir: P,
+    ) -> Result<Self, Error> {
+        let root_dir = root_dir.borrow();
+        collision::retry(DEFAULT_ATTEMPTS, || {
+            Self::new_with_name_in_exclusive(generator.generate(DIR_PREFIX), root_dir)
+        })
+        .await
     }
 
     /// Creates a new directory file in the specified location.
@@ -264,10 +382,8 @@ impl TempDir {
     /// * `path` - The path of the directory to wrap.
     /// * `ownership` - The ownership of the directory.
     pub async fn from_existing(path: PathBuf, ownership: Ownership) -> Result<Self, Error> {
-        if !path.is_dir() {
-            return Err(Error::InvalidDirectory);
-        }
-        Self::new_internal(path, ownership).await
+        check_directory(&path).await?;
+        Self::new_internal(path, ownership, false).await
     }
 
     /// Returns the path of the underlying temporary directory.
@@ -275,6 +391,22 @@ impl TempDir {
         &self.core.path
     }
 
+    /// Returns the path of the underlying temporary directory as a [`Path`] slice.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// assert!(dir.as_path().is_dir());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn as_path(&self) -> &Path {
+        self.core.path.as_path()
+    }
+
     /// Creates a new [`TempDir`] instance that shares the same underlying
     /// file handle as the existing [`TempDir`] instance.
     /// Reads, writes, and seeks will affect both [`TempDir`] instances simultaneously.
@@ -298,17 +430,266 @@ impl TempDir {
     /// # });
     /// ```
     pub fn ownership(&self) -> Ownership {
-        self.core.ownership
+        self.core.ownership.get()
     }
 
-    async fn new_internal<P: Borrow<Path>>(path: P, ownership: Ownership) -> Result<Self, Error> {
-        // Create the directory and all its parents.
-        tokio::fs::create_dir_all(path.borrow()).await?;
+    /// Keeps the directory and its contents when the last reference to it is dropped
+    /// and returns its path.
+    ///
+    /// This affects all references to the same directory, including those obtained via
+    /// [`TempDir::try_clone`], regardless of the order in which they are dropped.
+    /// This is useful to keep the results of an operation only if it succeeded.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// dir.write_file("artifact.bin", b"result").await?;
+    ///
+    /// let path = dir.persist()?;
+    /// assert!(path.join("artifact.bin").is_file());
+    /// # tokio::fs::remove_dir_all(path).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn persist(self) -> Result<PathBuf, Error> {
+        self.core.ownership.set(Ownership::Borrowed);
+        Ok(self.core.path.clone())
+    }
+
+    /// Creates a file at the specified path relative to this directory and writes the
+    /// specified content to it, creating missing parent directories as needed.
+    /// An existing file is overwritten. Returns the absolute path of the file.
+    ///
+    /// The file is not tracked separately; it is removed along with the directory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rel` - The path of the file, relative to this directory.
+    /// * `content` - The content to write to the file.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the path is absolute, empty, or would escape
+    /// the directory through `..` components, and [`Error::Io`] carrying the affected path
+    /// if the file or its parent directories could not be created or written.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// let path = dir.write_file("config/settings.toml", "debug = true").await?;
+    /// assert_eq!(path, dir.dir_path().join("config/settings.toml"));
+    /// assert_eq!(tokio::fs::read_to_string(path).await?, "debug = true");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn write_file<N: AsRef<Path>, B: AsRef<[u8]>>(
+        &self,
+        rel: N,
+        content: B,
+    ) -> Result<PathBuf, Error> {
+        let rel = rel.as_ref();
+        if !is_contained_file_path(rel) {
+            return Err(Error::InvalidFile);
+        }
+
+        let path = self.core.path.join(rel);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| Error::io_at(e, parent))?;
+        }
+
+        let mut file = tokio::fs::File::create(&path)
+            .await
+            .map_err(|e| Error::io_at(e, &path))?;
+        file.write_all(content.as_ref())
+            .await
+            .map_err(|e| Error::io_at(e, &path))?;
+        file.flush().await.map_err(|e| Error::io_at(e, &path))?;
+        Ok(path)
+    }
+
+    /// Creates a new temporary file in this directory that keeps the directory alive.
+    ///
+    /// Unlike files created via [`TempFile::new_in`], the file holds a reference to this
+    /// directory, so an owned directory is only removed after the file was deleted as well,
+    /// regardless of the order in which the directory and the file are dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// let dir_path = dir.dir_path().clone();
+    /// let file = dir.child_file().await?;
+    ///
+    /// // The directory is kept while the file is alive.
+    /// drop(dir);
+    /// assert!(file.file_path().is_file());
+    ///
+    /// drop(file);
+    /// assert!(!dir_path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn child_file(&self) -> Result<TempFile, Error> {
+        let file = TempFile::new_in(self.as_path()).await?;
+        Ok(file.bind_to_dir(self.try_clone().await?))
+    }
+
+    /// Creates a new temporary file with the specified name in this directory that keeps
+    /// the directory alive. See [`TempDir::child_file`] for details.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The file name to use.
+    pub async fn child_file_with_name<N: AsRef<str>>(&self, name: N) -> Result<TempFile, Error> {
+        let file = TempFile::new_with_name_in(name, self.as_path()).await?;
+        Ok(file.bind_to_dir(self.try_clone().await?))
+    }
+
+    /// Creates an owned file with the specified name in this directory.
+    ///
+    /// Like [`TempDir::child_file_with_name`], the file keeps this directory alive. It is
+    /// deleted when its last reference is dropped, or along with an owned directory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The file name to use.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is empty, `.` or `..`, or contains
+    /// path separators.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// let mut file = dir.create_file("output.log").await?;
+    /// file.write_all_and_flush(b"done").await?;
+    /// assert_eq!(file.file_path(), &dir.join("output.log"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn create_file<N: AsRef<str>>(&self, name: N) -> Result<TempFile, Error> {
+        if !is_valid_file_name(OsStr::new(name.as_ref())) {
+            return Err(Error::InvalidFile);
+        }
+        self.child_file_with_name(name).await
+    }
+
+    /// Creates an owned subdirectory with the specified name in this directory.
+    ///
+    /// The subdirectory and its contents are removed when its last reference is dropped,
+    /// or along with an owned parent directory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The directory name to use.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is empty, `.` or `..`, or contains
+    /// path separators.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// let nested = dir.create_dir("nested").await?;
+    /// let file = nested.create_file("file.txt").await?;
+    /// assert_eq!(file.file_path(), &dir.join("nested").join("file.txt"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn create_dir<N: AsRef<str>>(&self, name: N) -> Result<TempDir, Error> {
+        if !is_valid_file_name(OsStr::new(name.as_ref())) {
+            return Err(Error::InvalidFile);
+        }
+        Self::new_with_name_in(name, self.as_path()).await
+    }
+
+    /// Copies the specified file into this directory under a new random name and returns
+    /// an owned [`TempFile`] for the copy.
+    ///
+    /// The permissions of the source file are preserved. The copy is deleted when the last
+    /// reference to the returned file is dropped. Like files created via
+    /// [`TempDir::child_file`], it keeps this directory alive, so an owned directory is only
+    /// removed after the copy was deleted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `src` - The path of the file to copy.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] naming `src` if the file cannot be copied.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// # let uploads = TempDir::new().await?;
+    /// # let upload = uploads.write_file("upload.bin", b"payload").await?;
+    /// let workspace = TempDir::new().await?;
+    /// let file = workspace.import_file(upload.as_path()).await?;
+    /// assert_eq!(file.file_path().parent(), Some(workspace.as_path()));
+    /// assert_eq!(tokio::fs::read(file.file_path()).await?, b"payload");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn import_file<P: Borrow<Path>>(&self, src: P) -> Result<TempFile, Error> {
+        // A failed copy drops the owned file, removing it again.
+        let src = src.borrow();
+        let file = self.child_file().await?;
+        tokio::fs::copy(src, file.file_path())
+            .await
+            .map_err(|e| Error::io_at(e, src))?;
+        Ok(file)
+    }
+
+    /// Wraps an existing directory without taking ownership of it.
+    /// Unlike [`TempDir::from_existing`], this does not access the file system.
+    pub(crate) fn new_borrowed(path: PathBuf) -> Self {
+        let core = TempDirCore {
+            ownership: SharedOwnership::new(Ownership::Borrowed),
+            path: path.clone(),
+        };
+
+        Self {
+            dir: ManuallyDrop::new(path),
+            core: ManuallyDrop::new(Arc::new(core)),
+        }
+    }
+
+    async fn new_internal<P: Borrow<Path>>(
+        path: P,
+        ownership: Ownership,
+        exclusive: bool,
+    ) -> Result<Self, Error> {
+        let dir_path = PathBuf::from(path.borrow());
+        tokio::task::spawn_blocking(move || creation::create_dir(&dir_path, exclusive))
+            .await
+            .map_err(std::io::Error::other)??;
 
         let core = TempDirCore {
-            ownership,
+            ownership: SharedOwnership::new(ownership),
             path: PathBuf::from(path.borrow()),
         };
+        trace::created("directory", path.borrow(), ownership);
 
         Ok(Self {
             dir: ManuallyDrop::new(PathBuf::from(path.borrow())),
@@ -317,12 +698,28 @@ impl TempDir {
     }
 
     /// Gets the default temporary file directory.
+    ///
+    /// This must not be cached, so that runtime changes to the environment are honored.
     #[inline(always)]
     fn default_dir() -> PathBuf {
-        std::env::temp_dir()
+        crate::default_dir()
     }
 }
 
+/// Determines whether the path names a file inside a directory, i.e. whether it is
+/// a relative path consisting of normal components only, ignoring `.` components.
+fn is_contained_file_path(path: &Path) -> bool {
+    let mut components = 0;
+    for component in path.components() {
+        match component {
+            Component::Normal(_) => components += 1,
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    components > 0
+}
+
 /// Ensures the file handles are closed before the core reference is freed.
 /// If the core reference would be freed while handles are still open, it is
 /// possible that the underlying file cannot be deleted.
@@ -337,18 +734,16 @@ impl Drop for TempDir {
 /// Ensures that the underlying directory is deleted if this is an owned instance.
 /// If the underlying directory is not owned, this operation does nothing.
 impl Drop for TempDirCore {
-    /// See also [`TempDirCore::close`].
+    /// See also [`TempDir::close`].
     fn drop(&mut self) {
         // Ensure we don't drop borrowed directories.
-        if self.ownership != Ownership::Owned {
+        if self.ownership.get() != Ownership::Owned {
             return;
         }
 
-        // TODO: Use asynchronous variant if running in an async context.
-        // Note that if TempDir is used from the executor's handle,
-        //      this may block the executor itself.
-        // Using remove_dir_all to delete all content recursively.
-        let _ = std::fs::remove_dir_all(&self.path);
+        // Deleting all content recursively, continuing past entries that cannot be removed,
+        // without stalling the other tasks of a multi-threaded runtime.
+        offload::run_blocking(|| removal::remove_dir(&self.path));
     }
 }
 
@@ -396,6 +791,17 @@ mod tests {
     use super::*;
     use crate::TempFile;
 
+    #[test]
+    fn test_is_contained_file_path() {
+        assert!(is_contained_file_path(Path::new("file")));
+        assert!(is_contained_file_path(Path::new("./nested/file")));
+        assert!(!is_contained_file_path(Path::new("")));
+        assert!(!is_contained_file_path(Path::new(".")));
+        assert!(!is_contained_file_path(Path::new("../file")));
+        assert!(!is_contained_file_path(Path::new("nested/../../file")));
+        assert!(!is_contained_file_path(&std::env::temp_dir().join("file")));
+    }
+
     #[tokio::test]
     async fn test_new() -> Result<(), Error> {
         let dir = TempDir::new().await?;
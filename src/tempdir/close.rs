@@ -0,0 +1,66 @@
+use super::TempDir;
+use crate::removal::{self, remove_dir_all};
+use crate::{Error, Ownership};
+use std::mem::ManuallyDrop;
+use std::sync::Arc;
+
+impl TempDir {
+    /// Closes this reference to the directory and, if it was the last reference to an owned
+    /// directory, removes the directory and all of its contents.
+    ///
+    /// Unlike dropping the instance, the removal is performed on the blocking thread pool and
+    /// failures are reported. Like dropping, the removal is best-effort: entries that cannot
+    /// be removed are skipped, and on Unix, directories lacking the permissions required to
+    /// remove their entries are made accessible first.
+    ///
+    /// If other references to the directory remain, or if the directory is borrowed,
+    /// this does nothing and succeeds.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::RemovalFailed`] with the paths that could not be removed and the error
+    /// encountered removing the first of them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// let path = dir.dir_path().clone();
+    /// dir.write_file("nested/file.txt", "content").await?;
+    ///
+    /// dir.close().await?;
+    /// assert!(!path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[doc(alias = "drop_async")]
+    pub async fn close(self) -> Result<(), Error> {
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used again and its Drop implementation does not run.
+        let core = unsafe {
+            ManuallyDrop::drop(&mut this.dir);
+            ManuallyDrop::take(&mut this.core)
+        };
+
+        let core = match Arc::try_unwrap(core) {
+            Ok(core) => core,
+            Err(_) => return Ok(()),
+        };
+
+        // The directory is removed here rather than when dropping the core.
+        if core.ownership.release() != Ownership::Owned {
+            return Ok(());
+        }
+
+        let path = core.path.clone();
+        drop(core);
+
+        let failed = tokio::task::spawn_blocking(move || remove_dir_all(&path))
+            .await
+            .map_err(std::io::Error::other)?;
+        removal::into_result(failed)
+    }
+}
@@ -0,0 +1,122 @@
+use super::TempDir;
+use crate::Error;
+
+impl TempDir {
+    /// Returns an identifier of the file system the directory resides on.
+    ///
+    /// Two directories with the same identifier are located on the same file system,
+    /// so files can be atomically renamed between them. This is the device ID of the
+    /// directory as reported by `stat`.
+    ///
+    /// ## Platform support
+    ///
+    /// This method is available on Unix platforms only and returns [`Error::Unsupported`]
+    /// on all other platforms.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let first = TempDir::new().await?;
+    /// let second = TempDir::new().await?;
+    /// # #[cfg(unix)]
+    /// assert_eq!(first.filesystem_id().await?, second.filesystem_id().await?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn filesystem_id(&self) -> Result<u64, Error> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let metadata = tokio::fs::metadata(self.dir_path()).await?;
+            Ok(metadata.dev())
+        }
+
+        #[cfg(not(unix))]
+        {
+            Err(Error::Unsupported)
+        }
+    }
+
+    /// Returns the name of the file system type the directory resides on, e.g. `"tmpfs"`,
+    /// `"ext4"` or `"nfs"`.
+    ///
+    /// This is a best-effort lookup of the `f_type` magic number reported by `statfs`;
+    /// file system types that are not known to this crate yield `None`.
+    /// Note that ext2, ext3 and ext4 share the same magic number and are all reported as `"ext4"`.
+    ///
+    /// ## Platform support
+    ///
+    /// This method is available on Linux and Android only.
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub async fn filesystem_type_name(&self) -> Result<Option<&'static str>, Error> {
+        let path = self.dir_path().clone();
+        let magic = tokio::task::spawn_blocking(move || statfs_type(&path))
+            .await
+            .map_err(std::io::Error::other)??;
+        Ok(filesystem_type_name(magic))
+    }
+}
+
+/// Queries the file system type magic number of the specified path.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn statfs_type(path: &std::path::Path) -> std::io::Result<u32> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+    let mut stat = std::mem::MaybeUninit::<libc::statfs>::uninit();
+
+    // SAFETY: The path is a valid C string and `stat` is a valid out pointer.
+    if unsafe { libc::statfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    // SAFETY: statfs succeeded and initialized the structure.
+    let stat = unsafe { stat.assume_init() };
+
+    // The magic numbers fit into 32 bits; the field width differs between architectures.
+    Ok(stat.f_type as u32)
+}
+
+/// Maps a `statfs` magic number to a file system name.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn filesystem_type_name(magic: u32) -> Option<&'static str> {
+    let name = match magic {
+        0x0102_1994 => "tmpfs",
+        0x0000_EF53 => "ext4",
+        0x9123_683E => "btrfs",
+        0x5846_5342 => "xfs",
+        0x2FC1_2FC1 => "zfs",
+        0xF2F5_2010 => "f2fs",
+        0x794C_7630 => "overlay",
+        0x8584_58F6 => "ramfs",
+        0x0000_6969 => "nfs",
+        0xFF53_4D42 => "cifs",
+        0xFE53_4D42 => "smb2",
+        0x6573_5546 => "fuse",
+        0x0102_1997 => "9p",
+        0x0000_4D44 => "vfat",
+        0x2011_BAB0 => "exfat",
+        0x5346_544E => "ntfs",
+        0x7371_7368 => "squashfs",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(all(test, any(target_os = "linux", target_os = "android")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filesystem_type_name() {
+        assert_eq!(filesystem_type_name(0x0102_1994), Some("tmpfs"));
+        assert_eq!(filesystem_type_name(0xEF53), Some("ext4"));
+        assert_eq!(filesystem_type_name(0), None);
+    }
+}
@@ -0,0 +1,131 @@
+use super::TempDir;
+#[cfg(unix)]
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS, DIR_PREFIX};
+use crate::Error;
+use std::borrow::Borrow;
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+impl TempDir {
+    /// Creates a new temporary directory in the specified location that is only accessible
+    /// by the current user. When the instance goes out of scope, the directory will be deleted.
+    ///
+    /// Unlike [`TempDir::new_in`], the directory is not subject to the umask, so sensitive
+    /// data placed in it cannot be read by other users. It must not exist yet, so an existing
+    /// directory planted by another user is never reused; if a directory with the random name
+    /// already exists, a new name is tried, up to 16 names in total.
+    ///
+    /// ## Platform support
+    ///
+    /// On Unix, the directory is created with mode `0o700`, which is applied again after the
+    /// creation. On all other platforms, this behaves like [`TempDir::new_in`], and the
+    /// directory inherits the permissions of its parent.
+    ///
+    /// ## Arguments
+    ///
+    /// * `root_dir` - The directory to create the directory in.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new_secure_in(std::env::temp_dir()).await?;
+    ///
+    /// #[cfg(unix)]
+    /// {
+    ///     use std::os::unix::fs::PermissionsExt;
+    ///     let mode = tokio::fs::metadata(dir.dir_path()).await?.permissions().mode();
+    ///     assert_eq!(mode & 0o777, 0o700);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg(unix)]
+    pub async fn new_secure_in<P: Borrow<Path>>(root_dir: P) -> Result<Self, Error> {
+        Self::new_secure_named_in(root_dir.borrow(), || random_name(DIR_PREFIX)).await
+    }
+
+    /// Creates a new directory only accessible by the current user in the specified location,
+    /// named by `name`. Each name is created exclusively; if a directory with the name already
+    /// exists, a new name is tried, up to 16 names in total.
+    #[cfg(unix)]
+    async fn new_secure_named_in<F: FnMut() -> String>(
+        root_dir: &Path,
+        mut name: F,
+    ) -> Result<Self, Error> {
+        crate::errors::check_directory(root_dir).await?;
+        collision::retry(DEFAULT_ATTEMPTS, || {
+            Self::create_secure(root_dir.join(name()))
+        })
+        .await
+    }
+
+    /// Creates the directory at `path` with mode `0o700`, failing if it already exists.
+    #[cfg(unix)]
+    async fn create_secure(path: PathBuf) -> Result<Self, Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        tokio::fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&path)
+            .await
+            .map_err(|e| Error::io_at(e, &path))?;
+
+        // Wrap the directory right away, so that it is removed if restricting it fails.
+        let dir = Self::new_internal(path.as_path(), crate::Ownership::Owned, false).await?;
+        tokio::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o700))
+            .await
+            .map_err(|e| Error::io_at(e, &path))?;
+        Ok(dir)
+    }
+
+    /// Creates a new temporary directory in the specified location that is only accessible
+    /// by the current user. When the instance goes out of scope, the directory will be deleted.
+    ///
+    /// On this platform, this behaves like [`TempDir::new_in`].
+    #[cfg(not(unix))]
+    pub async fn new_secure_in<P: Borrow<Path>>(root_dir: P) -> Result<Self, Error> {
+        Self::new_in(root_dir).await
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[tokio::test]
+    async fn test_secure_names_never_reuse_existing_dirs() {
+        let root = TempDir::new().await.unwrap();
+        let existing = root.join("atmpd_0");
+        tokio::fs::create_dir(&existing).await.unwrap();
+        tokio::fs::write(existing.join("data.bin"), b"data")
+            .await
+            .unwrap();
+
+        let mut calls = 0;
+        let dir = TempDir::new_secure_named_in(root.as_path(), || {
+            calls += 1;
+            format!("atmpd_{}", calls - 1)
+        })
+        .await
+        .unwrap();
+        assert_eq!(calls, 2);
+        assert_eq!(dir.dir_path(), &root.join("atmpd_1"));
+        assert!(existing.join("data.bin").is_file());
+
+        // The retry gives up once all attempts collided.
+        let mut calls = 0;
+        let error = TempDir::new_secure_named_in(root.as_path(), || {
+            calls += 1;
+            "atmpd_0".to_string()
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(calls, DEFAULT_ATTEMPTS);
+        assert!(existing.join("data.bin").is_file());
+    }
+}
@@ -0,0 +1,178 @@
+use super::TempDir;
+use crate::Error;
+use std::path::{Path, PathBuf};
+
+impl TempDir {
+    /// Lists the contents of the directory recursively, descending at most `max_depth`
+    /// levels deep.
+    ///
+    /// Entries directly inside the directory are at depth 1, so a `max_depth` of `1` lists
+    /// the immediate children only and a `max_depth` of `0` lists nothing. Directories at
+    /// the limit are listed, but their contents are silently skipped.
+    ///
+    /// Symbolic links are listed but never followed, even if they point to a directory,
+    /// so symlink loops cannot cause unbounded work. Entries are listed level by level,
+    /// sorted by name within each directory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `max_depth` - The maximum depth of the listed entries.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if a directory could not be read.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// dir.write_file("a/b/c.txt", "deep").await?;
+    ///
+    /// let entries = dir.walk(2).await?;
+    /// assert_eq!(entries, [dir.join("a"), dir.join("a/b")]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn walk(&self, max_depth: usize) -> Result<Vec<PathBuf>, Error> {
+        let root = self.core.path.clone();
+        let entries = tokio::task::spawn_blocking(move || walk(&root, max_depth))
+            .await
+            .map_err(std::io::Error::other)??;
+        Ok(entries)
+    }
+
+    /// Returns an iterator over the entries directly inside the directory.
+    /// See [`tokio::fs::read_dir`] for details.
+    ///
+    /// This takes precedence over [`Path::read_dir`], which is accessible through
+    /// [`Deref`](std::ops::Deref) but blocks the runtime.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// dir.write_file("output.txt", "done").await?;
+    ///
+    /// let mut entries = dir.read_dir().await?;
+    /// while let Some(entry) = entries.next_entry().await? {
+    ///     assert_eq!(entry.file_name(), "output.txt");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn read_dir(&self) -> Result<tokio::fs::ReadDir, Error> {
+        tokio::fs::read_dir(&self.core.path)
+            .await
+            .map_err(|e| Error::io_at(e, &self.core.path))
+    }
+
+    /// Lists the entries directly inside the directory, sorted by name.
+    ///
+    /// An empty directory yields an empty list. See [`TempDir::walk`] to list
+    /// nested entries as well.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// assert!(dir.entries().await?.is_empty());
+    ///
+    /// dir.write_file("b.txt", "b").await?;
+    /// dir.write_file("a/c.txt", "c").await?;
+    /// assert_eq!(dir.entries().await?, [dir.join("a"), dir.join("b.txt")]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn entries(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut read_dir = self.read_dir().await?;
+        let mut entries = Vec::new();
+        while let Some(entry) = read_dir.next_entry().await? {
+            entries.push(entry.path());
+        }
+        entries.sort();
+        Ok(entries)
+    }
+
+    /// Determines the total size of all files in the directory and its subdirectories,
+    /// in bytes, descending at most `max_depth` levels deep.
+    ///
+    /// The size is the sum of the lengths of all regular files; directories count as zero.
+    /// Symbolic links are neither followed nor counted. Sparse or compressed files may
+    /// occupy less space on disk than reported. Files are counted if they are listed by
+    /// [`TempDir::walk`] for the same `max_depth`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `max_depth` - The maximum depth of the counted files.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if a directory or entry could not be read.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempDir;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = TempDir::new().await?;
+    /// dir.write_file("a.txt", "four").await?;
+    /// dir.write_file("nested/b.txt", "six!!!").await?;
+    /// assert_eq!(dir.size_on_disk(2).await?, 10);
+    /// assert_eq!(dir.size_on_disk(1).await?, 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn size_on_disk(&self, max_depth: usize) -> Result<u64, Error> {
+        let root = self.core.path.clone();
+        let size = tokio::task::spawn_blocking(move || {
+            walk(&root, max_depth)?
+                .into_iter()
+                .try_fold(0u64, |size, path| {
+                    // The metadata of a symbolic link describes the link itself.
+                    let metadata = std::fs::symlink_metadata(&path)?;
+                    Ok::<_, std::io::Error>(if metadata.is_file() {
+                        size.saturating_add(metadata.len())
+                    } else {
+                        size
+                    })
+                })
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        Ok(size)
+    }
+}
+
+/// Lists the contents of the directory up to the specified depth without following
+/// symbolic links. An explicit queue avoids deep recursion.
+fn walk(root: &Path, max_depth: usize) -> std::io::Result<Vec<PathBuf>> {
+    let mut entries = Vec::new();
+    let mut pending = std::collections::VecDeque::from([(root.to_path_buf(), 1)]);
+
+    while let Some((dir, depth)) = pending.pop_front() {
+        if depth > max_depth {
+            continue;
+        }
+
+        let mut children = std::fs::read_dir(&dir)?
+            .map(|entry| entry.and_then(|entry| Ok((entry.path(), entry.file_type()?))))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        children.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (path, file_type) in children {
+            // The file type of a symbolic link describes the link itself.
+            if file_type.is_dir() {
+                pending.push_back((path.clone(), depth + 1));
+            }
+            entries.push(path);
+        }
+    }
+
+    Ok(entries)
+}
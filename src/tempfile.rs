@@ -1,58 +1,95 @@
-//! # async-tempfile
+//! The tokio-based [`TempFile`].
 //!
-//! Provides the [`TempFile`] struct, an asynchronous wrapper based on `tokio::fs` for temporary
-//! files that will be automatically deleted when the last reference to the struct is dropped.
-//!
-//! ```
-//! use async_tempfile::TempFile;
-//!
-//! #[tokio::main]
-//! async fn main() {
-//!     let parent = TempFile::new().await.unwrap();
-//!
-//!     // The cloned reference will not delete the file when dropped.
-//!     {
-//!         let nested = parent.open_rw().await.unwrap();
-//!         assert_eq!(nested.file_path(), parent.file_path());
-//!         assert!(nested.file_path().is_file());
-//!     }
-//!
-//!     // The file still exists; it will be deleted when `parent` is dropped.
-//!     assert!(parent.file_path().is_file());
-//! }
-//! ```
-//!
-//! ## Features
-//!
-//! * `uuid` - (Default) Enables random file name generation based on the [`uuid`](https://crates.io/crates/uuid) crate.
-//!            Provides the `new` and `new_in`, as well as the `new_with_uuid*` group of methods.
+//! See the [crate documentation](crate#features) for the features extending this type.
 
-// Document crate features on docs.rs.
-#![cfg_attr(docsrs, feature(doc_cfg))]
-// Required for dropping the file.
 use std::borrow::{Borrow, BorrowMut};
 use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
 use std::io::{IoSlice, SeekFrom};
 use std::mem::ManuallyDrop;
 use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use tokio::fs::{File, OpenOptions};
-use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+use tokio::io::{
+    AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, BufReader,
+    BufWriter, ReadBuf,
+};
 
-#[cfg(not(feature = "uuid"))]
-use crate::random_name::RandomName;
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS, FILE_PREFIX};
+use crate::creation;
+use crate::errors::check_directory;
+use crate::offload;
+use crate::open_limit::{self, Permit};
+use crate::process_dir::{self, ProcessDir};
+use crate::removal;
+use crate::shared_path::SharedPath;
+use crate::trace;
+use crate::CountingTempFile;
+use crate::CreationInfo;
 use crate::Error;
-use crate::Ownership;
+use crate::NameGenerator;
+use crate::TempDir;
+use crate::TempFileBuilder;
+use crate::{Ownership, SharedOwnership};
 #[cfg(feature = "uuid")]
 use uuid::Uuid;
 
-const FILE_PREFIX: &str = "atmp_";
+#[cfg(target_os = "linux")]
+mod anonymous;
+mod atomic;
+mod beside;
+#[cfg(feature = "cap-std")]
+mod capability;
+mod copy;
+mod flush;
+mod lock;
+#[cfg(feature = "mime")]
+mod mime;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod persist;
+mod positional;
+mod remove;
+mod rename;
+mod reserve;
+mod shred;
+mod space;
+#[cfg(unix)]
+mod unix;
+#[cfg(windows)]
+mod windows;
+#[cfg(all(windows, feature = "windows-ads"))]
+mod windows_ads;
 
 /// A named temporary file that will be cleaned automatically
 /// after the last reference to it is dropped.
+///
+/// ## Flushing
+///
+/// Like [`tokio::fs::File`], writes are performed in the background: a write is considered
+/// complete once the data was handed to a background task, not when it reached the file.
+/// Dropping a handle does not discard such pending writes, but they may still be in flight
+/// after the handle was dropped. Call [`flush`](tokio::io::AsyncWriteExt::flush) before
+/// dropping a handle to a file that is kept, e.g. a borrowed file, to ensure all data was
+/// written when the handle is gone.
+///
+/// ## Cursor
+///
+/// Use [`AsyncSeekExt::rewind`] to move the cursor back to the start, e.g. to read what was
+/// just written, and [`AsyncSeekExt::stream_position`] to query it. The cursor belongs to the
+/// handle: it is shared with instances obtained via [`TempFile::try_clone`], but not with those
+/// obtained via [`TempFile::open_rw`] or [`TempFile::open_ro`].
+///
+/// ## Deletion
+///
+/// Owned files are deleted synchronously when the last reference is dropped, i.e. the file
+/// is gone once the drop returns. With the `rt-multi-thread` feature, when dropped on a worker
+/// thread of a multi-threaded tokio runtime, the other tasks of the worker are handed off to
+/// another thread during the deletion, so that the executor is not stalled. Use
+/// [`TempFile::close`] to delete a file asynchronously and observe errors.
 pub struct TempFile {
     /// A local reference to the file. Used to write to or read from the file.
     file: ManuallyDrop<File>,
@@ -66,8 +103,8 @@ pub struct TempFile {
 /// The instance that tracks the temporary file.
 /// If dropped, the file will be deleted.
 struct TempFileCore {
-    /// The path of the contained file.
-    path: PathBuf,
+    /// The path of the contained file, shared by all references to it.
+    path: SharedPath,
 
     /// Pointer to the file to keep it alive.
     file: ManuallyDrop<File>,
@@ -75,13 +112,72 @@ struct TempFileCore {
     /// A hacky approach to allow for "non-owned" files.
     /// If set to `Ownership::Owned`, the file specified in `path` will be deleted
     /// when this instance is dropped. If set to `Ownership::Borrowed`, the file will be kept.
-    ownership: Ownership,
+    /// The ownership is shared by all references to the file and can be changed at runtime.
+    ownership: SharedOwnership,
+
+    /// An optional function to call instead of deleting the file from the file system.
+    deleter: Mutex<Option<Deleter>>,
+
+    /// Whether to flush pending writes when references to the file are dropped.
+    flush_on_drop: std::sync::atomic::AtomicBool,
+
+    /// Whether to overwrite the contents of the file before deleting it on drop.
+    shred_on_drop: std::sync::atomic::AtomicBool,
+
+    /// Whether to remove all alternate data streams of the file when this instance is dropped.
+    #[cfg(all(windows, feature = "windows-ads"))]
+    remove_alternate_streams: std::sync::atomic::AtomicBool,
+
+    /// The capability of the directory containing the file, if the file was created through it.
+    /// If set, `path` is relative to this directory.
+    #[cfg(feature = "cap-std")]
+    cap_dir: Option<Arc<cap_std::fs::Dir>>,
+
+    /// The parameters the file was created with.
+    creation: CreationInfo,
+
+    /// Whether the file was explicitly removed via [`TempFile::remove`].
+    /// Only tracked in debug builds to report subsequent use of the file.
+    #[cfg(debug_assertions)]
+    removed: std::sync::atomic::AtomicBool,
+
+    /// Whether the file was created via `O_TMPFILE` and thus has no name.
+    /// If set, `path` is the directory containing the file.
+    #[cfg(target_os = "linux")]
+    anonymous: bool,
+
+    /// The per-process subdirectory containing the file, if any.
+    /// Keeps the directory alive until after the file was deleted.
+    #[allow(dead_code)]
+    process_dir: Option<Arc<ProcessDir>>,
+
+    /// The temporary directory containing the file, if the file is bound to it.
+    /// Keeps the directory alive until after the file was deleted.
+    #[allow(dead_code)]
+    parent: Option<TempDir>,
+
+    /// The permit counting the file against the limit of concurrently open files, if any.
+    /// Released after the file was closed and deleted.
+    #[allow(dead_code)]
+    permit: Permit,
 }
 
+/// A custom function deleting the file at the specified path.
+type Deleter = Box<dyn FnOnce(&Path) + Send + 'static>;
+
 impl TempFile {
     /// Creates a new temporary file in the default location.
     /// When the instance goes out of scope, the file will be deleted.
     ///
+    /// The default location is resolved via [`default_dir`](crate::default_dir) on every call,
+    /// so changes made via [`set_default_dir`](crate::set_default_dir) or to the environment at
+    /// runtime (e.g. of `TMPDIR` on Unix) apply to all files created afterwards.
+    ///
+    /// If [`TempFile::use_process_subdir`] is enabled, the file is placed into the per-process
+    /// subdirectory instead, which stays in the location it was created in for as long as it
+    /// contains files. Like with [`TempFile::new_in`], an existing file is never reused; the
+    /// name is generated anew on collisions.
+    ///
     /// ## Example
     ///
     /// ```
@@ -103,7 +199,7 @@ impl TempFile {
     /// # });
     /// ```
     pub async fn new() -> Result<Self, Error> {
-        Self::new_in(Self::default_dir()).await
+        Self::create_unique(None, DEFAULT_ATTEMPTS, || random_name(FILE_PREFIX)).await
     }
 
     /// Creates a new temporary file in the default location.
@@ -113,6 +209,12 @@ impl TempFile {
     ///
     /// * `name` - The name of the file to create in the default temporary directory.
     ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is not a plain file name, i.e. if it is empty,
+    /// `.` or `..`, or contains path separators, as is the case for absolute paths. The name is
+    /// rejected rather than sanitized, so that it cannot escape the directory.
+    ///
     /// ## Example
     ///
     /// ```
@@ -134,7 +236,12 @@ impl TempFile {
     /// # });
     /// ```
     pub async fn new_with_name<N: AsRef<str>>(name: N) -> Result<Self, Error> {
-        Self::new_with_name_in(name, Self::default_dir()).await
+        if !process_dir::is_enabled() {
+            return Self::new_with_name_in(name, Self::default_dir()).await;
+        }
+
+        let dir = ProcessDir::acquire()?;
+        Self::create_with_name_in(name, dir.path(), Some(dir.clone()), false).await
     }
 
     /// Creates a new temporary file in the default location.
@@ -168,12 +275,22 @@ impl TempFile {
     #[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
     #[cfg(feature = "uuid")]
     pub async fn new_with_uuid(uuid: Uuid) -> Result<Self, Error> {
-        Self::new_with_uuid_in(uuid, Self::default_dir()).await
+        let file_name = format!("{}{}", FILE_PREFIX, uuid);
+        Self::new_with_name(file_name).await
     }
 
     /// Creates a new temporary file in the specified location.
     /// When the instance goes out of scope, the file will be deleted.
     ///
+    /// The file is created with a random name that must not exist yet. Should a file with the
+    /// name exist, e.g. due to a collision of the names generated without the `uuid` feature,
+    /// a new name is generated, trying up to 16 names. Use [`TempFile::builder`] to configure
+    /// the number of attempts.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] with [`std::io::ErrorKind::AlreadyExists`] if all names were taken.
+    ///
     /// ## Crate Features
     ///
     /// * `uuid` - When the `uuid` crate feature is enabled, a random UUIDv4 is used to
@@ -203,28 +320,31 @@ impl TempFile {
     /// assert!(fs::metadata(file_path).await.is_err());
     /// # Ok::<(), Error>(())
     /// # });
+    /// ```
     pub async fn new_in<P: Borrow<Path>>(dir: P) -> Result<Self, Error> {
-        #[cfg(feature = "uuid")]
-        {
-            let id = Uuid::new_v4();
-            Self::new_with_uuid_in(id, dir).await
-        }
-
-        #[cfg(not(feature = "uuid"))]
-        {
-            let name = RandomName::new(FILE_PREFIX);
-            Self::new_with_name_in(name, dir).await
-        }
+        Self::create_unique(Some(dir.borrow()), DEFAULT_ATTEMPTS, || {
+            random_name(FILE_PREFIX)
+        })
+        .await
     }
 
     /// Creates a new temporary file in the specified location.
     /// When the instance goes out of scope, the file will be deleted.
     ///
+    /// If a file with the name already exists, it is opened and owned by the instance, i.e.
+    /// deleted on drop. Use [`TempFile::new_with_name_in_exclusive`] to fail instead.
+    ///
     /// ## Arguments
     ///
     /// * `dir` - The directory to create the file in.
     /// * `name` - The file name to use.
     ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is not a plain file name, i.e. if it is empty,
+    /// `.` or `..`, or contains path separators, as is the case for absolute paths. The name is
+    /// rejected rather than sanitized, so that it cannot escape the directory.
+    ///
     /// ## Example
     ///
     /// ```
@@ -250,14 +370,140 @@ impl TempFile {
         name: N,
         dir: P,
     ) -> Result<Self, Error> {
-        let dir = dir.borrow();
-        if !dir.is_dir() {
-            return Err(Error::InvalidDirectory);
-        }
-        let file_name = name.as_ref();
-        let mut path = PathBuf::from(dir);
-        path.push(file_name);
-        Self::new_internal(path, Ownership::Owned).await
+        Self::create_with_name_in(name, dir.borrow(), None, false).await
+    }
+
+    /// Creates a new temporary file with the specified name in the specified location,
+    /// failing if a file with that name already exists.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// Unlike [`TempFile::new_with_name_in`], which opens and takes ownership of an existing
+    /// file of the same name, this never takes ownership of a file it did not create, so that
+    /// someone else's file is not deleted on drop. This is recommended for caller-supplied names.
+    /// The check and the creation are a single atomic operation.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The file name to use.
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] with [`std::io::ErrorKind::AlreadyExists`] if the file exists,
+    /// and [`Error::InvalidFile`] if the name is not a plain file name.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{ErrorKind, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = std::env::temp_dir();
+    /// let file = TempFile::new_with_name_in_exclusive("exclusive.file", dir.as_path()).await?;
+    ///
+    /// let error = TempFile::new_with_name_in_exclusive("exclusive.file", dir.as_path())
+    ///     .await
+    ///     .unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+    /// # drop(file);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn new_with_name_in_exclusive<N: AsRef<str>, P: Borrow<Path>>(
+        name: N,
+        dir: P,
+    ) -> Result<Self, Error> {
+        Self::create_with_name_in(name, dir.borrow(), None, true).await
+    }
+
+    /// Creates a new temporary file in the specified location, named by the specified generator.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// The file is created exclusively; if a file with the generated name already exists,
+    /// the generator is asked for another name, up to 16 names in total.
+    ///
+    /// ## Arguments
+    ///
+    /// * `generator` - The generator to obtain the file name from.
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the generated name is not a valid file name, and an
+    /// error of kind [`ErrorKind::AlreadyExists`](crate::ErrorKind::AlreadyExists) if all
+    /// generated names were taken.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{RandomNameGenerator, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new_with_generator(&RandomNameGenerator, std::env::temp_dir()).await?;
+    /// assert!(file.file_path().is_file());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn new_with_generator<G: NameGenerator + ?Sized, P: Borrow<Path>>(
+        generator: &G,
+        dir: P,
+    ) -> Result<Self, Error> {
+        Self::create_unique(Some(dir.borrow()), DEFAULT_ATTEMPTS, || {
+            generator.generate(FILE_PREFIX)
+        })
+        .await
+    }
+
+    /// Creates a new owned file in the specified directory, or in the default location if
+    /// `None`, named by `name`. Each name is created exclusively; if a file with the name
+    /// already exists, a new name is tried, up to `attempts` names in total.
+    pub(crate) async fn create_unique<F: FnMut() -> String>(
+        dir: Option<&Path>,
+        attempts: u32,
+        mut name: F,
+    ) -> Result<Self, Error> {
+        let process_dir = match dir {
+            None if process_dir::is_enabled() => Some(ProcessDir::acquire()?),
+            _ => None,
+        };
+        let default_dir;
+        let dir = match (&process_dir, dir) {
+            (Some(process_dir), _) => process_dir.path(),
+            (None, Some(dir)) => dir,
+            (None, None) => {
+                default_dir = Self::default_dir();
+                default_dir.as_path()
+            }
+        };
+
+        collision::retry(attempts, || {
+            Self::create_with_name_in(name(), dir, process_dir.clone(), true)
+        })
+        .await
+    }
+
+    /// Creates a new owned file with the specified name in the specified directory,
+    /// optionally keeping the per-process subdirectory alive while the file exists.
+    /// If `exclusive` is set, creation fails if the file already exists.
+    async fn create_with_name_in<N: AsRef<str>>(
+        name: N,
+        dir: &Path,
+        process_dir: Option<Arc<ProcessDir>>,
+        exclusive: bool,
+    ) -> Result<Self, Error> {
+        let name = name.as_ref().to_owned();
+        let dir_path = dir.to_path_buf();
+        let permit = open_limit::acquire().await;
+        let (file, path) =
+            tokio::task::spawn_blocking(move || creation::create_file(&name, &dir_path, exclusive))
+                .await
+                .map_err(std::io::Error::other)??;
+        Self::wrap(
+            File::from_std(file),
+            permit,
+            path.as_path(),
+            Ownership::Owned,
+            process_dir,
+        )
+        .await
     }
 
     /// Creates a new temporary file in the specified location.
@@ -309,37 +555,299 @@ impl TempFile {
         path: P,
         ownership: Ownership,
     ) -> Result<Self, Error> {
-        if !path.borrow().is_file() {
-            return Err(Error::InvalidFile);
+        match tokio::fs::metadata(path.borrow()).await {
+            Ok(metadata) if metadata.is_file() => {}
+            _ => return Err(Error::InvalidFile),
+        }
+        Self::new_internal(path, ownership, None).await
+    }
+
+    /// Wraps a new instance of this type around an existing file, opened with the specified
+    /// options.
+    ///
+    /// The supplied `options` override the defaults of [`TempFile::from_existing`], which opens
+    /// the file for reading and writing without truncating it. This allows wrapping a file
+    /// read-only, or truncating it when wrapping. The options apply to the returned handle
+    /// only; handles obtained via [`TempFile::open_rw`] and [`TempFile::open_ro`] use their
+    /// respective modes.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The path of the file to wrap.
+    /// * `ownership` - The ownership of the file.
+    /// * `options` - The options to open the file with. Reading must be enabled.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the path does not refer to an existing file, and
+    /// [`Error::Io`] with [`std::io::ErrorKind::InvalidInput`] if the options do not enable
+    /// reading.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{Ownership, TempFile};
+    /// # use tokio::fs::OpenOptions;
+    /// # let _ = tokio_test::block_on(async {
+    /// let existing = TempFile::from_bytes(b"contents").await?;
+    ///
+    /// let mut options = OpenOptions::new();
+    /// options.read(true);
+    /// let mut file = TempFile::from_existing_with_options(
+    ///     existing.file_path().as_path(),
+    ///     Ownership::Borrowed,
+    ///     options,
+    /// )
+    /// .await?;
+    ///
+    /// assert_eq!(file.read_all().await?, b"contents");
+    /// assert!(file.write_all_and_flush(b"denied").await.is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn from_existing_with_options<P: Borrow<Path>>(
+        path: P,
+        ownership: Ownership,
+        options: OpenOptions,
+    ) -> Result<Self, Error> {
+        match tokio::fs::metadata(path.borrow()).await {
+            Ok(metadata) if metadata.is_file() => {}
+            _ => return Err(Error::InvalidFile),
+        }
+
+        // The file is only wrapped once validated, so that it is not deleted on failure.
+        let (file, permit) = Self::open_internal(path.borrow(), &options, true).await?;
+        Self::wrap(file, permit, path.borrow(), ownership, None).await
+    }
+
+    /// Wraps a new instance of this type around an opened [`std::fs::File`] whose path is known,
+    /// e.g. a handle obtained via [`TempFile::into_std`] or from a synchronous API.
+    ///
+    /// The handle is used as is, including its access mode and cursor position; it is not
+    /// verified that it actually refers to the file at `path`. If `ownership` is set to
+    /// [`Ownership::Owned`], the file at `path` is deleted when the last reference is dropped.
+    ///
+    /// ## Arguments
+    ///
+    /// * `file` - The opened file.
+    /// * `path` - The path of the file.
+    /// * `ownership` - The ownership of the file.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the handle does not refer to a regular file, and
+    /// an error of kind [`std::io::ErrorKind::InvalidInput`] if it was not opened for reading.
+    /// The file is not deleted in either case.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{Ownership, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::from_bytes(b"contents").await?;
+    /// let path = file.file_path().clone();
+    /// let std_file: std::fs::File = file.into_std().await?;
+    ///
+    /// let mut file = TempFile::from_std(std_file, path.as_path(), Ownership::Owned).await?;
+    /// assert_eq!(file.read_all().await?, b"contents");
+    ///
+    /// drop(file);
+    /// assert!(!path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn from_std<P: Borrow<Path>>(
+        file: std::fs::File,
+        path: P,
+        ownership: Ownership,
+    ) -> Result<Self, Error> {
+        let file = File::from_std(file);
+        match file.metadata().await {
+            Ok(metadata) if metadata.is_file() => {}
+            _ => return Err(Error::InvalidFile),
         }
-        Self::new_internal(path, ownership).await
+
+        // The file is only wrapped once validated, so that it is not deleted on failure.
+        check_readable(&file).await?;
+        Self::wrap(file, None, path.borrow(), ownership, None).await
     }
 
     /// Returns the path of the underlying temporary file.
     pub fn file_path(&self) -> &PathBuf {
-        &self.core.path
+        self.core.path.get()
+    }
+
+    /// Returns the path of the underlying temporary file as a [`Path`] slice.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// assert!(file.as_path().is_file());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn as_path(&self) -> &Path {
+        self.core.path.get().as_path()
+    }
+
+    /// Returns the directory containing the temporary file as a [`TempDir`].
+    ///
+    /// The returned directory is [`Ownership::Borrowed`], i.e. it will not be
+    /// deleted when the handle is dropped, regardless of the ownership of this file.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidDirectory`] if the file path has no parent directory.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{Ownership, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// let dir = file.parent_dir()?;
+    /// assert_eq!(dir.ownership(), Ownership::Borrowed);
+    /// assert_eq!(Some(dir.dir_path().as_path()), file.file_path().parent());
+    ///
+    /// // Dropping the directory handle leaves the directory in place.
+    /// drop(dir);
+    /// assert!(file.file_path().is_file());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn parent_dir(&self) -> Result<TempDir, Error> {
+        match self.core.path.get().parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => {
+                Ok(TempDir::new_borrowed(parent.to_path_buf()))
+            }
+            _ => Err(Error::InvalidDirectory),
+        }
     }
 
     /// Opens a new TempFile instance in read-write mode.
+    ///
+    /// The file is opened anew, so the instance has its own cursor, starting at the beginning
+    /// of the file. Handles to the same file come in two flavors:
+    ///
+    /// * Handles obtained via [`TempFile::try_clone`] duplicate the existing handle and share
+    ///   its cursor: seeking, reading or writing through one instance moves the position
+    ///   of the other.
+    /// * Handles obtained via [`TempFile::open_rw`] and [`TempFile::open_ro`] open the file
+    ///   again and have independent cursors, each starting at the beginning of the file.
+    ///
+    /// In both cases, the instances refer to the same file, so data written through one
+    /// instance is visible to the others once flushed.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] if the file no longer exists, e.g. because it was
+    /// deleted by another process.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncSeekExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.write_all_and_flush(b"data").await?;
+    ///
+    /// let mut reopened = file.open_rw().await?;
+    /// assert_eq!(reopened.stream_position().await?, 0);
+    /// assert_eq!(file.stream_position().await?, 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
     pub async fn open_rw(&self) -> Result<TempFile, Error> {
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(&self.core.path)
-            .await?;
+        let file = self.core.open(Access::ReadWrite).await?;
         Ok(TempFile {
             core: self.core.clone(),
             file: ManuallyDrop::new(file),
         })
     }
 
+    /// Opens the file anew in read-write mode, obtaining an instance with its own cursor.
+    ///
+    /// This is the same as [`TempFile::open_rw`]; unlike [`TempFile::try_clone`], the cursor
+    /// of the new instance is independent of the cursor of this one.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] if the file no longer exists, e.g. because it was
+    /// deleted by another process.
+    pub async fn reopen(&self) -> Result<TempFile, Error> {
+        self.open_rw().await
+    }
+
+    /// Obtains another instance of the file with its own cursor, as opposed to
+    /// [`TempFile::try_clone`], which shares the cursor.
+    ///
+    /// This is the same as [`TempFile::open_rw`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] if the file no longer exists, e.g. because it was
+    /// deleted by another process.
+    pub async fn clone_independent_cursor(&self) -> Result<TempFile, Error> {
+        self.open_rw().await
+    }
+
     /// Opens a new TempFile instance in read-only mode.
+    ///
+    /// Like [`TempFile::open_rw`], the instance has its own cursor.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] if the file no longer exists, e.g. because it was
+    /// deleted by another process.
     pub async fn open_ro(&self) -> Result<TempFile, Error> {
-        let file = OpenOptions::new()
-            .read(true)
-            .write(false)
-            .open(&self.core.path)
-            .await?;
+        let file = self.core.open(Access::Read).await?;
+        Ok(TempFile {
+            core: self.core.clone(),
+            file: ManuallyDrop::new(file),
+        })
+    }
+
+    /// Opens a new TempFile instance in append mode, e.g. to collect output from multiple tasks.
+    ///
+    /// Every write through the instance lands at the end of the file at the time of the write,
+    /// regardless of the cursor position and of seeks; on Unix, the operating system moves to the
+    /// end and writes atomically, so that writes of multiple append instances, or of other
+    /// processes appending to the file, never overwrite each other. The instance can be read from
+    /// and has its own cursor like one obtained via [`TempFile::open_rw`].
+    ///
+    /// Note that each write of the [`AsyncWrite`] implementation may only write part of the
+    /// buffer, and that [`AsyncWriteExt::write_all`] may issue multiple writes. To keep records
+    /// from interleaving, write each of them with a single call, e.g. via
+    /// [`TempFile::write_all_and_flush`] with a buffer of moderate size.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] if the file no longer exists, e.g. because it was
+    /// deleted by another process.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncSeekExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.write_all_and_flush(b"first\n").await?;
+    ///
+    /// let mut log = file.open_append().await?;
+    /// log.rewind().await?;
+    /// log.write_all_and_flush(b"second\n").await?;
+    ///
+    /// assert_eq!(file.read_all().await?, b"first\nsecond\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn open_append(&self) -> Result<TempFile, Error> {
+        let file = self.core.open(Access::Append).await?;
         Ok(TempFile {
             core: self.core.clone(),
             file: ManuallyDrop::new(file),
@@ -349,46 +857,1064 @@ impl TempFile {
     /// Creates a new TempFile instance that shares the same underlying
     /// file handle as the existing TempFile instance.
     /// Reads, writes, and seeks will affect both TempFile instances simultaneously.
+    ///
+    /// **Note:** The cursor is shared, i.e. seeking or reading through one instance moves
+    /// the position of the other, as both refer to the same open file description.
+    /// This is the behavior of duplicated handles on all platforms and is intended, e.g. for
+    /// appending to a file from multiple tasks in turn. Use
+    /// [`TempFile::clone_independent_cursor`] to obtain an instance with a separate cursor.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// let mut clone = file.try_clone().await?;
+    /// file.write_all(b"data").await?;
+    /// assert_eq!(clone.stream_position().await?, 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] if the platform reports the file as missing.
+    /// Since the existing handle is duplicated, this usually succeeds even if the file
+    /// was deleted.
     #[allow(dead_code)]
     pub async fn try_clone(&self) -> Result<TempFile, Error> {
+        self.core.check_not_removed()?;
+        let file = self
+            .file
+            .try_clone()
+            .await
+            .map_err(|e| self.core.vanished(e))?;
         Ok(TempFile {
             core: self.core.clone(),
-            file: ManuallyDrop::new(self.file.try_clone().await?),
+            file: ManuallyDrop::new(file),
         })
     }
 
-    /// Determines the ownership of the temporary file.
-    /// ### Example
+    /// Creates another reference to the same file synchronously, like [`TempFile::try_clone`].
+    ///
+    /// The reference shares the core of this instance, so the file is only deleted once the
+    /// last reference is dropped. The existing handle is duplicated, so the reference shares
+    /// the cursor as well. Duplicating a handle does not block, but writes still buffered in
+    /// this instance are not flushed first; they complete as usual.
+    ///
+    /// `TempFile` does not implement [`Clone`], as duplicating the handle can fail,
+    /// e.g. when the process runs out of file descriptors.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if the handle cannot be duplicated, and [`Error::Unsupported`]
+    /// on platforms other than Unix and Windows.
+    ///
+    /// ## Example
+    ///
     /// ```
-    /// # use async_tempfile::{Ownership, TempFile};
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::{AsyncSeekExt, AsyncWriteExt};
     /// # let _ = tokio_test::block_on(async {
-    /// let file = TempFile::new().await?;
-    /// assert_eq!(file.ownership(), Ownership::Owned);
-    /// # drop(file);
+    /// let mut file = TempFile::new().await?;
+    /// let mut reference = file.clone_ref()?;
+    /// assert_eq!(file.reference_count(), 2);
+    ///
+    /// file.write_all(b"data").await?;
+    /// file.flush().await?;
+    /// assert_eq!(reference.stream_position().await?, 4);
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// # });
     /// ```
-    pub fn ownership(&self) -> Ownership {
-        self.core.ownership
+    pub fn clone_ref(&self) -> Result<TempFile, Error> {
+        self.core.check_not_removed()?;
+        let file = match duplicate(&self.file) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::Unsupported => {
+                return Err(Error::Unsupported)
+            }
+            Err(e) => return Err(self.core.vanished(e)),
+        };
+        Ok(TempFile {
+            core: self.core.clone(),
+            file: ManuallyDrop::new(File::from_std(file)),
+        })
     }
 
-    async fn new_internal<P: Borrow<Path>>(path: P, ownership: Ownership) -> Result<Self, Error> {
+    /// Creates a new temporary file in the default location, containing the specified data.
+    ///
+    /// The data is written and flushed, and the cursor is rewound to the start, so the
+    /// returned file is ready to be read from.
+    ///
+    /// ## Arguments
+    ///
+    /// * `data` - The initial contents of the file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncReadExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::from_bytes(b"fixture").await?;
+    ///
+    /// let mut content = String::new();
+    /// file.read_to_string(&mut content).await?;
+    /// assert_eq!(content, "fixture");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let file = Self::new().await?;
+        file.with_contents(data).await
+    }
+
+    /// Creates a new temporary file in the specified location, containing the specified data.
+    ///
+    /// The data is written and flushed, and the cursor is rewound to the start, so the
+    /// returned file is ready to be read from.
+    ///
+    /// ## Arguments
+    ///
+    /// * `data` - The initial contents of the file.
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = std::env::temp_dir();
+    /// let file = TempFile::from_bytes_in(b"fixture", dir.as_path()).await?;
+    /// assert_eq!(tokio::fs::read(file.file_path()).await?, b"fixture");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn from_bytes_in<P: Borrow<Path>>(data: &[u8], dir: P) -> Result<Self, Error> {
+        let file = Self::new_in(dir).await?;
+        file.with_contents(data).await
+    }
+
+    /// Creates a new temporary file in the default location, containing the data read from
+    /// the specified reader.
+    ///
+    /// The reader is streamed into the file until it reaches the end, without buffering
+    /// the entire data in memory. The file is flushed and rewound to the start, ready to be
+    /// read back. Returns the file along with the number of bytes copied.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader` - The reader providing the contents of the file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut upload = &b"uploaded data"[..];
+    /// let (mut file, copied) = TempFile::from_reader(&mut upload).await?;
+    /// assert_eq!(copied, 13);
+    /// assert_eq!(file.read_all().await?, b"uploaded data");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<(Self, u64), Error> {
+        let mut file = Self::new().await?;
+        let copied = file.fill_from(reader).await?;
+        Ok((file, copied))
+    }
+
+    /// Writes the initial contents of a new file and rewinds it.
+    async fn with_contents(mut self, data: &[u8]) -> Result<Self, Error> {
+        self.write_all_and_flush(data).await?;
+        self.file.rewind().await?;
+        Ok(self)
+    }
+
+    /// Replaces the contents of the file with the data read from the specified reader.
+    ///
+    /// The file is truncated, the reader is copied in until it reaches the end, and the file
+    /// is flushed and rewound to the start, ready to be read back. Returns the number of
+    /// bytes written.
+    ///
+    /// This is useful to refill a file adopted via [`TempFile::from_existing`].
+    /// Note that the file is truncated even if reading from the reader fails.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader` - The reader providing the new contents of the file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncReadExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// let written = file.fill_from(&b"new contents"[..]).await?;
+    /// assert_eq!(written, 12);
+    ///
+    /// let mut content = String::new();
+    /// file.read_to_string(&mut content).await?;
+    /// assert_eq!(content, "new contents");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn fill_from<R: AsyncRead + Unpin>(&mut self, mut reader: R) -> Result<u64, Error> {
+        self.core.check_not_removed()?;
+        self.file.set_len(0).await?;
+        self.file.rewind().await?;
+        let written = tokio::io::copy(&mut reader, &mut *self.file).await?;
+        self.file.flush().await?;
+        self.file.rewind().await?;
+        Ok(written)
+    }
+
+    /// Writes all of the data at the current position and flushes the file.
+    ///
+    /// Unlike [`AsyncWriteExt::write_all`], the data is visible to other handles to the file
+    /// once this returns. The method is not named `write_all`, as an inherent method of that
+    /// name would shadow [`AsyncWriteExt::write_all`] when called as `file.write_all(..)`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `data` - The data to write.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.write_all_and_flush(b"payload").await?;
+    /// assert_eq!(file.read_all().await?, b"payload");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[doc(alias = "write_all_buf")]
+    pub async fn write_all_and_flush(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.file.write_all(data).await?;
+        self.file.flush().await?;
+        Ok(())
+    }
+
+    /// Reads the entire contents of the file.
+    ///
+    /// The cursor is rewound to the start first, so the data is read in full even right
+    /// after writing. Afterwards, the cursor is at the end of the file. Use
+    /// [`AsyncReadExt::read_to_end`] to read the remainder of the file from the current
+    /// position only; the method is not named `read_to_end`, as an inherent method of that
+    /// name would shadow [`AsyncReadExt::read_to_end`] when called as `file.read_to_end(..)`.
+    ///
+    /// See [`TempFile::write_all_and_flush`] for an example.
+    pub async fn read_all(&mut self) -> Result<Vec<u8>, Error> {
+        self.file.rewind().await?;
+        let mut buf = Vec::new();
+        self.file.read_to_end(&mut buf).await?;
+        Ok(buf)
+    }
+
+    /// Reads up to `len` bytes from the current position without advancing it, so that
+    /// subsequent reads return the same data again.
+    ///
+    /// Fewer bytes are returned if the end of the file is reached. The position is restored
+    /// even if reading fails.
+    ///
+    /// **Note:** The cursor is moved transiently and shared with all instances obtained via
+    /// [`TempFile::try_clone`]. Peeking is therefore not safe while such an instance is used
+    /// concurrently; use [`TempFile::open_rw`] to avoid sharing the cursor.
+    ///
+    /// ## Arguments
+    ///
+    /// * `len` - The maximum number of bytes to read.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncReadExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.fill_from(&b"MAGIC payload"[..]).await?;
+    /// assert_eq!(file.peek(5).await?, b"MAGIC");
+    ///
+    /// let mut content = String::new();
+    /// file.read_to_string(&mut content).await?;
+    /// assert_eq!(content, "MAGIC payload");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn peek(&mut self, len: usize) -> Result<Vec<u8>, Error> {
+        self.core.check_not_removed()?;
+        let position = self.file.stream_position().await?;
+
+        let mut buf = Vec::new();
+        let result = (&mut *self.file)
+            .take(len as u64)
+            .read_to_end(&mut buf)
+            .await;
+
+        self.file.seek(SeekFrom::Start(position)).await?;
+        result?;
+        Ok(buf)
+    }
+
+    /// Determines the ownership of the temporary file.
+    /// ### Example
+    /// ```
+    /// # use async_tempfile::{Ownership, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// assert_eq!(file.ownership(), Ownership::Owned);
+    /// # drop(file);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn ownership(&self) -> Ownership {
+        self.core.ownership.get()
+    }
+
+    /// Returns the number of references to the file, i.e. the number of instances sharing it
+    /// through [`TempFile::open_rw`], [`TempFile::open_ro`] or [`TempFile::try_clone`].
+    ///
+    /// This is intended for debugging handle leaks. Other threads may create or drop
+    /// references concurrently, so the result may be outdated immediately.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// assert_eq!(file.reference_count(), 1);
+    ///
+    /// let clone = file.open_ro().await?;
+    /// assert_eq!(file.reference_count(), 2);
+    /// assert!(!file.is_last_reference());
+    ///
+    /// drop(clone);
+    /// assert!(file.is_last_reference());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn reference_count(&self) -> usize {
+        Arc::strong_count(&self.core)
+    }
+
+    /// Determines whether this is the only reference to the file, i.e. whether
+    /// dropping it deletes an owned file. See [`TempFile::reference_count`].
+    pub fn is_last_reference(&self) -> bool {
+        self.reference_count() == 1
+    }
+
+    /// Determines whether dropping this instance deletes the file, i.e. whether the file is
+    /// owned and this is the last reference to it.
+    ///
+    /// Unlike [`TempFile::ownership`], this accounts for other references keeping the file
+    /// alive. Like [`TempFile::reference_count`], the result may be outdated immediately if
+    /// other threads create or drop references, or change the ownership concurrently.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// assert!(file.will_delete_on_drop());
+    ///
+    /// let clone = file.try_clone().await?;
+    /// assert!(!file.will_delete_on_drop());
+    ///
+    /// drop(clone);
+    /// assert!(file.will_delete_on_drop());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn will_delete_on_drop(&self) -> bool {
+        self.ownership() == Ownership::Owned && self.is_last_reference()
+    }
+
+    /// Takes ownership of the file, so that it will be deleted when the last
+    /// reference to it is dropped.
+    ///
+    /// The ownership is shared by all references to the same file, i.e. this
+    /// affects all handles obtained via [`TempFile::open_rw`], [`TempFile::open_ro`]
+    /// and [`TempFile::try_clone`] as well.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{Ownership, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// # let path = TempFile::new().await?.into_borrowed().file_path().clone();
+    /// let file = TempFile::from_existing(path.clone(), Ownership::Borrowed)
+    ///     .await?
+    ///     .into_owned();
+    /// assert_eq!(file.ownership(), Ownership::Owned);
+    ///
+    /// drop(file);
+    /// assert!(!path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn into_owned(self) -> Self {
+        self.core.ownership.set(Ownership::Owned);
+        self
+    }
+
+    /// Gives up ownership of the file, so that it will be kept when the last
+    /// reference to it is dropped.
+    ///
+    /// The ownership is shared by all references to the same file, i.e. this
+    /// affects all handles obtained via [`TempFile::open_rw`], [`TempFile::open_ro`]
+    /// and [`TempFile::try_clone`] as well.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{Ownership, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?.into_borrowed();
+    /// assert_eq!(file.ownership(), Ownership::Borrowed);
+    ///
+    /// let path = file.file_path().clone();
+    /// drop(file);
+    /// assert!(path.is_file());
+    /// # tokio::fs::remove_file(path).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn into_borrowed(self) -> Self {
+        self.core.ownership.set(Ownership::Borrowed);
+        self
+    }
+
+    /// Keeps the file when the last reference to it is dropped and returns its path.
+    ///
+    /// Like [`TempFile::into_borrowed`], this affects all references to the same file.
+    /// Handles obtained via [`TempFile::open_rw`], [`TempFile::open_ro`] and
+    /// [`TempFile::try_clone`] remain open and usable; the file is kept regardless
+    /// of the order in which they are dropped. A custom deleter set via
+    /// [`TempFile::with_deleter`] is not called.
+    ///
+    /// For files created through a `cap-std` directory capability, the returned
+    /// path is relative to the capability.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] in debug builds if the file was removed via
+    /// [`TempFile::remove`], and [`Error::Unsupported`] for anonymous files.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncWriteExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.write_all(b"result").await?;
+    /// file.flush().await?;
+    ///
+    /// let path = file.persist()?;
+    /// assert_eq!(tokio::fs::read(&path).await?, b"result");
+    /// # tokio::fs::remove_file(path).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn persist(self) -> Result<PathBuf, Error> {
+        self.core.check_not_removed()?;
+        #[cfg(target_os = "linux")]
+        if self.core.anonymous {
+            return Err(Error::Unsupported);
+        }
+
+        self.core.ownership.set(Ownership::Borrowed);
+        Ok(self.core.path.get().clone())
+    }
+
+    /// Gives up ownership of the file and returns its path, closing this handle.
+    ///
+    /// Like [`TempFile::persist`], this affects all references to the same file, but performs
+    /// no checks and cannot fail. Afterwards, the file is no longer managed by this crate:
+    /// the caller is responsible for deleting it, e.g. once external tooling is done with it.
+    ///
+    /// For anonymous files, the returned path does not name the file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// let path = file.detach();
+    /// assert!(path.is_file());
+    ///
+    /// // The file must be deleted manually.
+    /// tokio::fs::remove_file(path).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn detach(self) -> PathBuf {
+        self.core.ownership.set(Ownership::Borrowed);
+        self.core.path.get().clone()
+    }
+
+    /// Keeps the file when the last reference to it is dropped and returns the handle
+    /// of this instance, e.g. to pass it to an API taking a [`File`] by value.
+    ///
+    /// Like [`TempFile::persist`], this affects all references to the same file, so the
+    /// handle of this instance is returned regardless of whether other references remain;
+    /// they stay open and usable, and the file is not deleted by any of them. The returned
+    /// handle shares its cursor with handles obtained via [`TempFile::try_clone`].
+    ///
+    /// Pending writes are not flushed; the returned handle completes them as usual.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] in debug builds if the file was removed via
+    /// [`TempFile::remove`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncWriteExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// let path = file.file_path().clone();
+    ///
+    /// let mut inner: tokio::fs::File = file.into_file()?;
+    /// inner.write_all(b"kept").await?;
+    /// inner.flush().await?;
+    /// drop(inner);
+    ///
+    /// assert_eq!(tokio::fs::read(&path).await?, b"kept");
+    /// # tokio::fs::remove_file(path).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn into_file(self) -> Result<File, Error> {
+        self.core.check_not_removed()?;
+        self.core.ownership.set(Ownership::Borrowed);
+
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used again and its Drop implementation does not run.
+        let (file, core) = unsafe {
+            (
+                ManuallyDrop::take(&mut this.file),
+                ManuallyDrop::take(&mut this.core),
+            )
+        };
+        drop(core);
+        Ok(file)
+    }
+
+    /// Keeps the file when the last reference to it is dropped and returns the handle of this
+    /// instance as a [`std::fs::File`], e.g. to pass it to a synchronous API.
+    ///
+    /// This behaves like [`TempFile::into_file`]: the ownership is given up for all references
+    /// to the same file, and the caller becomes responsible for deleting it. Pending writes are
+    /// completed before the handle is returned. Use [`TempFile::from_std`] to wrap the handle
+    /// again.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FileVanished`] in debug builds if the file was removed via
+    /// [`TempFile::remove`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use std::io::Read;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::from_bytes(b"contents").await?;
+    /// let path = file.file_path().clone();
+    ///
+    /// let mut std_file: std::fs::File = file.into_std().await?;
+    /// let mut content = String::new();
+    /// std_file.read_to_string(&mut content)?;
+    /// assert_eq!(content, "contents");
+    /// # drop(std_file);
+    /// # tokio::fs::remove_file(path).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn into_std(self) -> Result<std::fs::File, Error> {
+        Ok(self.into_file()?.into_std().await)
+    }
+
+    /// Returns the parameters the file was created with, e.g. for logging or for creating
+    /// equivalent files. See [`CreationInfo`] for details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{Ownership, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new_with_name_in("data.bin", std::env::temp_dir()).await?;
+    /// let info = file.creation_info();
+    /// assert_eq!(info.directory(), std::env::temp_dir());
+    /// assert_eq!(info.name(), "data.bin");
+    /// assert_eq!(info.ownership(), Ownership::Owned);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn creation_info(&self) -> &CreationInfo {
+        &self.core.creation
+    }
+
+    /// Queries the metadata of the file.
+    ///
+    /// The metadata is obtained through the open file handle, so it reflects the file even
+    /// after it was renamed. Data still buffered in this handle is not accounted for; flush
+    /// the handle first to include it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncWriteExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.write_all(b"data").await?;
+    /// file.flush().await?;
+    /// assert!(file.metadata().await?.is_file());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn metadata(&self) -> Result<std::fs::Metadata, Error> {
+        Ok(self.file.metadata().await?)
+    }
+
+    /// Returns the size of the file in bytes. See [`TempFile::metadata`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncWriteExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// assert!(file.is_empty().await?);
+    ///
+    /// file.write_all(b"data").await?;
+    /// file.flush().await?;
+    /// assert_eq!(file.len().await?, 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn len(&self) -> Result<u64, Error> {
+        Ok(self.metadata().await?.len())
+    }
+
+    /// Determines whether the file is empty. See [`TempFile::metadata`].
+    pub async fn is_empty(&self) -> Result<bool, Error> {
+        Ok(self.len().await? == 0)
+    }
+
+    /// Truncates or extends the file to the specified size in bytes.
+    ///
+    /// All references to the file share the same underlying file, so the size changes for
+    /// every handle, including those obtained via [`TempFile::open_rw`], [`TempFile::open_ro`]
+    /// and [`TempFile::try_clone`]. The cursor is not moved; see [`TempFile::truncate`] to
+    /// empty the file and rewind it. Extending the file fills it with zeros.
+    ///
+    /// ## Arguments
+    ///
+    /// * `size` - The new size of the file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::from_bytes(b"some data").await?;
+    /// file.set_len(4).await?;
+    /// assert_eq!(file.len().await?, 4);
+    ///
+    /// file.set_len(16).await?;
+    /// assert_eq!(file.len().await?, 16);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn set_len(&self, size: u64) -> Result<(), Error> {
+        self.core.check_not_removed()?;
+        Ok(self.file.set_len(size).await?)
+    }
+
+    /// Empties the file and rewinds the cursor to the start.
+    ///
+    /// Like [`TempFile::set_len`], this affects all references to the file. Only the cursor
+    /// of this instance (and of handles sharing it via [`TempFile::try_clone`]) is rewound.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::from_bytes(b"old").await?;
+    /// file.truncate().await?;
+    /// file.write_all_and_flush(b"new").await?;
+    /// assert_eq!(file.read_all().await?, b"new");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn truncate(&mut self) -> Result<(), Error> {
+        self.set_len(0).await?;
+        self.file.rewind().await?;
+        Ok(())
+    }
+
+    /// Flushes all data and metadata of the file to disk, e.g. before persisting it via
+    /// [`TempFile::persist_to`]. See [`File::sync_all`] for details.
+    ///
+    /// Pending writes must be flushed before via [`flush`](AsyncWriteExt::flush).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.write_all_and_flush(b"durable").await?;
+    /// file.sync_all().await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn sync_all(&self) -> Result<(), Error> {
+        self.core.check_not_removed()?;
+        Ok(self.file.sync_all().await?)
+    }
+
+    /// Changes the permissions of the file, for all references to it.
+    /// See [`File::set_permissions`] for details.
+    ///
+    /// ## Arguments
+    ///
+    /// * `perm` - The new permissions of the file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::os::unix::fs::PermissionsExt;
+    /// file.set_permissions(std::fs::Permissions::from_mode(0o640)).await?;
+    /// assert_eq!(file.metadata().await?.permissions().mode() & 0o777, 0o640);
+    /// # }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn set_permissions(&self, perm: std::fs::Permissions) -> Result<(), Error> {
+        self.core.check_not_removed()?;
+        Ok(self.file.set_permissions(perm).await?)
+    }
+
+    /// Flushes the data of the file to disk, without necessarily syncing its metadata.
+    /// See [`File::sync_data`] for details, and [`TempFile::sync_all`] for an example.
+    pub async fn sync_data(&self) -> Result<(), Error> {
+        self.core.check_not_removed()?;
+        Ok(self.file.sync_data().await?)
+    }
+
+    /// Wraps this instance in a [`CountingTempFile`] that keeps track of
+    /// the number of bytes written through it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncWriteExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?.counting();
+    /// file.write_all(b"some data").await?;
+    /// assert_eq!(file.bytes_written(), 9);
+    ///
+    /// // The wrapped file can be retrieved again.
+    /// let file = file.into_inner();
+    /// # drop(file);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn counting(self) -> CountingTempFile {
+        CountingTempFile::new(self)
+    }
+
+    /// Wraps this instance in a [`BufWriter`], e.g. for writing many small records.
+    ///
+    /// The wrapper keeps the file alive like any other reference. Data is held in the buffer
+    /// until it is full or flushed; flush the wrapper via [`AsyncWriteExt::flush`] before
+    /// reading the file through another handle, persisting it or handing out its path, and
+    /// before dropping it, as buffered data is lost otherwise. The file can be retrieved
+    /// again via [`BufWriter::into_inner`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncWriteExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut writer = TempFile::new().await?.buf_writer();
+    /// for line in ["first", "second"] {
+    ///     writer.write_all(line.as_bytes()).await?;
+    ///     writer.write_all(b"\n").await?;
+    /// }
+    /// writer.flush().await?;
+    ///
+    /// let mut file = writer.into_inner();
+    /// assert_eq!(file.read_all().await?, b"first\nsecond\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn buf_writer(self) -> BufWriter<TempFile> {
+        BufWriter::new(self)
+    }
+
+    /// Wraps this instance in a [`BufReader`], e.g. for reading the file line by line.
+    ///
+    /// Reading starts at the current cursor position; rewind the file first to read data
+    /// that was just written, e.g. via [`AsyncSeekExt::rewind`]. The file can be retrieved
+    /// again via [`BufReader::into_inner`], discarding any data that was buffered but not read.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncBufReadExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::from_bytes(b"first\nsecond\n").await?;
+    ///
+    /// let mut lines = file.buf_reader().lines();
+    /// assert_eq!(lines.next_line().await?.as_deref(), Some("first"));
+    /// assert_eq!(lines.next_line().await?.as_deref(), Some("second"));
+    /// assert_eq!(lines.next_line().await?, None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn buf_reader(self) -> BufReader<TempFile> {
+        BufReader::new(self)
+    }
+
+    /// Returns a [`TempFileBuilder`] to create a file with a custom name pattern and location.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::builder()
+    ///     .prefix("upload_")
+    ///     .extension("bin")
+    ///     .dir(std::env::temp_dir())
+    ///     .build()
+    ///     .await?;
+    /// assert_eq!(file.file_path().extension().unwrap(), "bin");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn builder() -> TempFileBuilder {
+        TempFileBuilder::new(FILE_PREFIX)
+    }
+
+    /// Replaces the deletion of the file with a custom function.
+    ///
+    /// When the last reference to an owned file is dropped, the `deleter` is called
+    /// with the path of the file instead of removing it from the file system.
+    /// This allows using [`TempFile`] as a cleanup guard for resources that are keyed
+    /// by the path, e.g. objects in a remote store. The function is never called
+    /// for [`Ownership::Borrowed`] files.
+    ///
+    /// The deleter applies to all references sharing the same file; registering
+    /// a new deleter replaces a previously registered one.
+    ///
+    /// ## Synchronous execution
+    ///
+    /// The deleter is invoked synchronously from within `Drop` of the last reference,
+    /// which may run on an executor thread. It must therefore not block for extended periods
+    /// of time and must not panic.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use std::sync::{Arc, Mutex};
+    /// # let _ = tokio_test::block_on(async {
+    /// let deleted = Arc::new(Mutex::new(None));
+    /// let file = TempFile::new().await?.with_deleter({
+    ///     let deleted = deleted.clone();
+    ///     move |path| {
+    ///         let _ = std::fs::remove_file(path);
+    ///         *deleted.lock().unwrap() = Some(path.to_path_buf());
+    ///     }
+    /// });
+    ///
+    /// let path = file.file_path().clone();
+    /// drop(file);
+    /// assert_eq!(deleted.lock().unwrap().as_ref(), Some(&path));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn with_deleter<F>(self, deleter: F) -> Self
+    where
+        F: FnOnce(&Path) + Send + 'static,
+    {
+        if let Ok(mut slot) = self.core.deleter.lock() {
+            *slot = Some(Box::new(deleter));
+        }
+        self
+    }
+
+    /// Keeps the directory alive until after this newly created file was deleted.
+    pub(crate) fn bind_to_dir(mut self, dir: TempDir) -> Self {
+        // Newly created files are not shared yet.
+        if let Some(core) = Arc::get_mut(&mut self.core) {
+            core.parent = Some(dir);
+        }
+        self
+    }
+
+    /// Enables or disables placing files created in the default location into a
+    /// per-process subdirectory of the temporary directory, e.g. `/tmp/atmpd_1234/`.
+    ///
+    /// This affects files created in the default location afterwards, e.g. via [`TempFile::new`]
+    /// and [`TempFile::new_with_name`]; files created in an explicitly specified directory are
+    /// not affected. The subdirectory is created lazily by the first file placed into it and is
+    /// removed again after the last of these files was deleted, unless it still contains
+    /// other entries, such as files that were converted via [`TempFile::into_borrowed`].
+    ///
+    /// This is disabled by default.
+    ///
+    /// ## Arguments
+    ///
+    /// * `enabled` - Whether to use the per-process subdirectory.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// TempFile::use_process_subdir(true);
+    ///
+    /// let file = TempFile::new().await?;
+    /// let dir = file.file_path().parent().unwrap().to_path_buf();
+    /// assert_eq!(dir.parent(), Some(async_tempfile::default_dir().as_path()));
+    ///
+    /// // The subdirectory is removed along with the last file in it.
+    /// drop(file);
+    /// assert!(!dir.exists());
+    /// # TempFile::use_process_subdir(false);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn use_process_subdir(enabled: bool) {
+        process_dir::set_enabled(enabled);
+    }
+
+    /// Limits the number of files kept open concurrently by this crate, or removes the limit.
+    ///
+    /// Creating many files at once, e.g. via `futures::future::join_all`, may otherwise exhaust
+    /// the available file descriptors. When limited, constructors opening files wait until
+    /// one of the open files was dropped. A file counts against the limit from its creation
+    /// until the last reference to it is dropped; additional handles to the same file, e.g.
+    /// via [`TempFile::open_rw`] and [`TempFile::open_ro`], are not counted separately.
+    ///
+    /// The limit is global to the process and unlimited by default. Changing it does not
+    /// affect files that are already open or operations that are already waiting. A limit
+    /// of zero is treated as one.
+    ///
+    /// ## Arguments
+    ///
+    /// * `max` - The maximum number of concurrently open files, or `None` for no limit.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// TempFile::set_max_concurrent_opens(Some(8));
+    /// let file = TempFile::new().await?;
+    /// # TempFile::set_max_concurrent_opens(None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn set_max_concurrent_opens(max: Option<usize>) {
+        open_limit::set_max_concurrent_opens(max);
+    }
+
+    async fn new_internal<P: Borrow<Path>>(
+        path: P,
+        ownership: Ownership,
+        process_dir: Option<Arc<ProcessDir>>,
+    ) -> Result<Self, Error> {
+        let mut options = OpenOptions::new();
+        options
+            .create(ownership == Ownership::Owned)
+            .read(true)
+            .write(true);
         let path = path.borrow();
+        let (file, permit) = Self::open_internal(path, &options, false).await?;
+        Self::wrap(file, permit, path, ownership, process_dir).await
+    }
 
+    /// Opens the file with the specified options, optionally ensuring it is readable.
+    ///
+    /// Returns the permit counting the file against the limit of concurrently open files
+    /// along with the file; it must be kept until the file is closed.
+    async fn open_internal(
+        path: &Path,
+        options: &OpenOptions,
+        readable: bool,
+    ) -> Result<(File, Permit), Error> {
+        let permit = open_limit::acquire().await;
+        let file = options
+            .open(path)
+            .await
+            .map_err(|e| Error::io_at(e, path))?;
+        if readable {
+            check_readable(&file).await?;
+        }
+        Ok((file, permit))
+    }
+
+    /// Wraps an instance of this type around the opened file, keeping the permit the file
+    /// was opened with for as long as the file exists.
+    async fn wrap(
+        file: File,
+        permit: Permit,
+        path: &Path,
+        ownership: Ownership,
+        process_dir: Option<Arc<ProcessDir>>,
+    ) -> Result<Self, Error> {
+        // The file is opened once; the handle kept by the core is derived from it.
         let core = TempFileCore {
-            file: ManuallyDrop::new(
-                OpenOptions::new()
-                    .create(ownership == Ownership::Owned)
-                    .read(false)
-                    .write(true)
-                    .open(path)
-                    .await?,
-            ),
-            ownership,
-            path: PathBuf::from(path),
+            file: ManuallyDrop::new(file.try_clone().await?),
+            ownership: SharedOwnership::new(ownership),
+            path: SharedPath::new(PathBuf::from(path)),
+            creation: CreationInfo::new(path, ownership),
+            deleter: Mutex::new(None),
+            flush_on_drop: std::sync::atomic::AtomicBool::new(false),
+            shred_on_drop: std::sync::atomic::AtomicBool::new(false),
+            #[cfg(debug_assertions)]
+            removed: std::sync::atomic::AtomicBool::new(false),
+            #[cfg(all(windows, feature = "windows-ads"))]
+            remove_alternate_streams: std::sync::atomic::AtomicBool::new(false),
+            #[cfg(feature = "cap-std")]
+            cap_dir: None,
+            #[cfg(target_os = "linux")]
+            anonymous: false,
+            process_dir,
+            parent: None,
+            permit,
         };
+        trace::created("file", path, ownership);
 
-        let file = OpenOptions::new().read(true).write(true).open(path).await?;
         Ok(Self {
             file: ManuallyDrop::new(file),
             core: ManuallyDrop::new(Arc::new(core)),
@@ -396,19 +1922,191 @@ impl TempFile {
     }
 
     /// Gets the default temporary file directory.
+    ///
+    /// This must not be cached, so that runtime changes to the environment are honored.
     #[inline(always)]
     fn default_dir() -> PathBuf {
-        std::env::temp_dir()
+        crate::default_dir()
     }
 }
 
+/// The access mode of a handle opened anew for an existing file.
+#[derive(Debug, Clone, Copy)]
+enum Access {
+    /// Reading only.
+    Read,
+    /// Reading and writing at the cursor position.
+    ReadWrite,
+    /// Reading, and writing at the end of the file.
+    Append,
+}
+
+impl Access {
+    /// Determines whether the handle may be written to.
+    fn write(self) -> bool {
+        !matches!(self, Access::Read)
+    }
+
+    /// Determines whether writes are appended to the end of the file.
+    fn append(self) -> bool {
+        matches!(self, Access::Append)
+    }
+}
+
+/// Duplicates the handle for synchronous access.
+fn duplicate(file: &File) -> std::io::Result<std::fs::File> {
+    #[cfg(unix)]
+    {
+        use std::os::fd::AsFd;
+        Ok(file.as_fd().try_clone_to_owned()?.into())
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::io::AsHandle;
+        Ok(file.as_handle().try_clone_to_owned()?.into())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = file;
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+}
+
+/// Fails if the file was not opened for reading.
+async fn check_readable(file: &File) -> Result<(), Error> {
+    let mut probe = file.try_clone().await?.into_std().await;
+
+    // Reading zero bytes checks the access mode without consuming any data.
+    let result = tokio::task::spawn_blocking(move || std::io::Read::read(&mut probe, &mut []))
+        .await
+        .map_err(std::io::Error::other)?;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(_) => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "the file must be opened for reading",
+        )
+        .into()),
+    }
+}
+
+impl TempFileCore {
+    /// Opens a new read handle to the file, optionally allowing writes.
+    async fn open(&self, access: Access) -> Result<File, Error> {
+        self.check_not_removed()?;
+
+        #[cfg(feature = "cap-std")]
+        if let Some(dir) = &self.cap_dir {
+            return capability::open(dir, self.path.get(), access)
+                .await
+                .map_err(|e| self.vanished(e));
+        }
+
+        // Anonymous files can only be reopened through their descriptor.
+        #[cfg(target_os = "linux")]
+        if self.anonymous {
+            return Ok(OpenOptions::new()
+                .read(true)
+                .write(access.write())
+                .append(access.append())
+                .open(anonymous::reopen_path(self))
+                .await?);
+        }
+
+        OpenOptions::new()
+            .read(true)
+            .write(access.write())
+            .append(access.append())
+            .open(self.path.get())
+            .await
+            .map_err(|e| self.vanished(e))
+    }
+
+    /// Deletes the file, using the custom deleter if one was set.
+    fn delete(&self) -> std::io::Result<()> {
+        // Anonymous files are reclaimed by the kernel.
+        #[cfg(target_os = "linux")]
+        if self.anonymous {
+            return Ok(());
+        }
+
+        // A custom deleter replaces the removal from the file system.
+        let deleter = self
+            .deleter
+            .lock()
+            .ok()
+            .and_then(|mut deleter| deleter.take());
+        if let Some(deleter) = deleter {
+            deleter(self.path.get());
+            return Ok(());
+        }
+
+        // Files created through a capability are removed through it as well.
+        #[cfg(feature = "cap-std")]
+        if let Some(dir) = &self.cap_dir {
+            return dir.remove_file(self.path.get());
+        }
+
+        std::fs::remove_file(self.path.get())
+    }
+
+    /// Fails with [`Error::FileVanished`] if the file was removed via [`TempFile::remove`].
+    ///
+    /// This is only checked in debug builds; release builds report whatever error
+    /// the operation on the removed file produces.
+    fn check_not_removed(&self) -> Result<(), Error> {
+        #[cfg(debug_assertions)]
+        if self.removed.load(std::sync::atomic::Ordering::Acquire) {
+            return Err(Error::FileVanished(self.path.get().clone()));
+        }
+        Ok(())
+    }
+
+    /// Maps errors indicating that the file no longer exists to [`Error::FileVanished`].
+    fn vanished(&self, error: std::io::Error) -> Error {
+        if error.kind() == std::io::ErrorKind::NotFound {
+            Error::FileVanished(self.path.get().clone())
+        } else {
+            Error::io_at(error, self.path.get().clone())
+        }
+    }
+}
+
+/// Determines whether an I/O error indicates that the parent directory of a file does not exist
+/// or is not a directory.
+fn is_missing_directory(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        std::io::ErrorKind::NotFound | std::io::ErrorKind::NotADirectory
+    )
+}
+
+/// Replaces an I/O error creating the file at `path` with a more specific error if it
+/// indicates a missing parent directory and the directory does not exist or is not a directory.
+async fn describe_missing_directory(error: std::io::Error, path: &Path, dir: &Path) -> Error {
+    if is_missing_directory(&error) {
+        if let Err(dir_error) = check_directory(dir).await {
+            return dir_error;
+        }
+    }
+    Error::io_at(error, path)
+}
+
 /// Ensures the file handles are closed before the core reference is freed.
 /// If the core reference would be freed while handles are still open, it is
 /// possible that the underlying file cannot be deleted.
 impl Drop for TempFile {
     fn drop(&mut self) {
         // Ensure all file handles are closed before we attempt to delete the file itself via core.
-        drop(unsafe { ManuallyDrop::take(&mut self.file) });
+        let file = unsafe { ManuallyDrop::take(&mut self.file) };
+        if self.flush_on_drop() {
+            flush::flush_blocking(file);
+        } else {
+            drop(file);
+        }
         drop(unsafe { ManuallyDrop::take(&mut self.core) });
     }
 }
@@ -417,24 +2115,45 @@ impl Drop for TempFile {
 /// If the underlying file is not owned, this operation does nothing.
 impl Drop for TempFileCore {
     fn drop(&mut self) {
+        // Closing the file handle first, as otherwise the file might not be deleted.
+        // This handle is never written to, so closing it cannot lose any data; the file is
+        // shredded through a duplicate of it along with the deletion. It is closed for borrowed
+        // files as well, as it would be leaked otherwise.
+        let file = unsafe { ManuallyDrop::take(&mut self.file) };
+        let shred = if self.ownership.get() == Ownership::Owned {
+            let shred = self.shred_handle(&file);
+            drop(file);
+            shred
+        } else {
+            self.sync_on_drop(file);
+            None
+        };
+
+        #[cfg(all(windows, feature = "windows-ads"))]
+        if *self.remove_alternate_streams.get_mut() {
+            windows_ads::remove_alternate_streams(self.path.get());
+        }
+
         // Ensure we don't drop borrowed files.
-        if self.ownership != Ownership::Owned {
+        if self.ownership.get() != Ownership::Owned {
             return;
         }
 
-        // Closing the file handle first, as otherwise the file might not be deleted.
-        drop(unsafe { ManuallyDrop::take(&mut self.file) });
-
-        // TODO: Use asynchronous variant if running in an async context.
-        // Note that if TempFile is used from the executor's handle,
-        //      this may block the executor itself.
-        let _ = std::fs::remove_file(&self.path);
+        // Shredding and deleting synchronously, without stalling the other tasks of
+        // a multi-threaded runtime.
+        let result = offload::run_blocking(|| {
+            if let Some(file) = shred {
+                shred::shred_and_close(file);
+            }
+            self.delete()
+        });
+        removal::report_file_removal(self.path.get(), &result);
     }
 }
 
 impl Debug for TempFileCore {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.path)
+        write!(f, "{:?}", self.path.get())
     }
 }
 
@@ -444,6 +2163,46 @@ impl Debug for TempFile {
     }
 }
 
+/// Compares the paths of the files.
+///
+/// Temporary files created independently always have distinct paths, so two instances are
+/// equal if they refer to the same file, e.g. a file and the references obtained from it via
+/// [`TempFile::open_rw`] or [`TempFile::try_clone`]. Instances wrapping the same existing file via
+/// [`TempFile::from_existing`] compare equal as well.
+///
+/// **Note:** The path changes when the file is renamed, e.g. via [`TempFile::rename`]. Files
+/// kept in a hashed collection must not be renamed, just like other keys must not be modified.
+///
+/// ## Example
+///
+/// ```
+/// # use async_tempfile::TempFile;
+/// # use std::collections::HashSet;
+/// # let _ = tokio_test::block_on(async {
+/// let file = TempFile::new().await?;
+/// let other = TempFile::new().await?;
+///
+/// let files = HashSet::from([file.try_clone().await?, file.open_ro().await?, other]);
+/// assert_eq!(files.len(), 2);
+/// assert!(files.contains(&file));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+impl PartialEq for TempFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.file_path() == other.file_path()
+    }
+}
+
+impl Eq for TempFile {}
+
+/// Hashes the path of the file, consistent with [`PartialEq`].
+impl Hash for TempFile {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.file_path().hash(state);
+    }
+}
+
 /// Allows implicit treatment of TempFile as a File.
 impl Deref for TempFile {
     type Target = File;
@@ -0,0 +1,121 @@
+use super::{describe_missing_directory, TempFile, TempFileCore};
+use crate::open_limit;
+use crate::shared_path::SharedPath;
+use crate::trace;
+use crate::{CreationInfo, Error, Ownership, SharedOwnership};
+use std::borrow::Borrow;
+use std::ffi::CString;
+use std::mem::ManuallyDrop;
+use std::os::fd::FromRawFd;
+use std::os::unix::ffi::OsStringExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::fs::File;
+
+impl TempFile {
+    /// Creates a new anonymous temporary file in the specified directory using `O_TMPFILE`.
+    ///
+    /// The file never appears in the directory and is reclaimed by the kernel once the last
+    /// handle to it is closed, even if the process crashes. Consequently:
+    ///
+    /// * [`TempFile::file_path`] returns the path of the directory.
+    /// * Additional handles obtained via [`TempFile::open_rw`] and [`TempFile::open_ro`] are
+    ///   opened through `/proc/self/fd`.
+    /// * Deleting the file is a no-op; custom deleters set via [`TempFile::with_deleter`]
+    ///   are not called.
+    /// * Renaming or persisting the file fails with [`Error::Unsupported`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Unsupported`] if the kernel or the file system of the directory
+    /// does not support `O_TMPFILE`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{Error, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = std::env::temp_dir();
+    /// let file = match TempFile::new_anonymous_in(dir.as_path()).await {
+    ///     Ok(file) => file,
+    ///     Err(Error::Unsupported) => return Ok(()),
+    ///     Err(e) => return Err(e.into()),
+    /// };
+    /// assert_eq!(file.file_path(), &dir);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub async fn new_anonymous_in<P: Borrow<Path>>(dir: P) -> Result<Self, Error> {
+        let dir = dir.borrow();
+        let permit = open_limit::acquire().await;
+        let file = match open_anonymous(dir.to_path_buf()).await {
+            Ok(file) => File::from_std(file),
+            Err(e) if is_unsupported(&e) => return Err(Error::Unsupported),
+            Err(e) => return Err(describe_missing_directory(e, dir, dir).await),
+        };
+
+        let core = TempFileCore {
+            file: ManuallyDrop::new(file.try_clone().await?),
+            ownership: SharedOwnership::new(Ownership::Owned),
+            creation: CreationInfo::new_unnamed(dir, Ownership::Owned),
+            path: SharedPath::new(dir.to_path_buf()),
+            deleter: Mutex::new(None),
+            flush_on_drop: std::sync::atomic::AtomicBool::new(false),
+            shred_on_drop: std::sync::atomic::AtomicBool::new(false),
+            #[cfg(debug_assertions)]
+            removed: std::sync::atomic::AtomicBool::new(false),
+            #[cfg(feature = "cap-std")]
+            cap_dir: None,
+            anonymous: true,
+            process_dir: None,
+            parent: None,
+            permit,
+        };
+        trace::created("file", dir, Ownership::Owned);
+
+        Ok(Self {
+            file: ManuallyDrop::new(file),
+            core: ManuallyDrop::new(Arc::new(core)),
+        })
+    }
+}
+
+/// Opens an unnamed file in the directory on the blocking thread pool.
+async fn open_anonymous(dir: PathBuf) -> std::io::Result<std::fs::File> {
+    tokio::task::spawn_blocking(move || {
+        let path = CString::new(dir.into_os_string().into_vec())?;
+        let flags = libc::O_TMPFILE | libc::O_RDWR | libc::O_CLOEXEC;
+
+        // SAFETY: The path is a valid, NUL-terminated string.
+        let fd = unsafe { libc::open(path.as_ptr(), flags, 0o600 as libc::c_uint) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        // SAFETY: The descriptor was just opened and is exclusively owned by the file.
+        Ok(unsafe { std::fs::File::from_raw_fd(fd) })
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+/// Determines whether the error indicates that `O_TMPFILE` is not supported.
+///
+/// Kernels predating `O_TMPFILE` only interpret its `O_DIRECTORY` part, failing with `EISDIR`.
+fn is_unsupported(error: &std::io::Error) -> bool {
+    matches!(
+        error.raw_os_error(),
+        Some(libc::EOPNOTSUPP) | Some(libc::EISDIR)
+    )
+}
+
+/// Returns the path to reopen the anonymous file through.
+pub(super) fn reopen_path(core: &TempFileCore) -> PathBuf {
+    use std::os::fd::AsRawFd;
+    PathBuf::from(format!("/proc/self/fd/{}", core.file.as_raw_fd()))
+}
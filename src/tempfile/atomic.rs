@@ -0,0 +1,87 @@
+use super::beside::parent_dir;
+use super::TempFile;
+use crate::Error;
+use std::borrow::Borrow;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+
+impl TempFile {
+    /// Atomically replaces the contents of the target file with the specified data.
+    ///
+    /// The data is written to a temporary file in the directory of the target, synced to disk,
+    /// and renamed over the target, which is created if it does not exist yet. Finally, the
+    /// directory is synced to persist the rename (on Unix only). Readers of the target thus
+    /// observe either the old or the new contents, but never a partially written file.
+    ///
+    /// If any step before the rename fails, the temporary file is removed and the
+    /// target is left untouched.
+    ///
+    /// ## Arguments
+    ///
+    /// * `target` - The path of the file to replace.
+    /// * `data` - The new contents of the file.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] with the path involved in the failing step: the temporary file
+    /// while writing and syncing it, the target when renaming, and its directory when syncing
+    /// the directory.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{TempDir, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// # let dir = TempDir::new().await?;
+    /// let target = dir.join("settings.json");
+    /// TempFile::save_atomic(target.as_path(), b"{\"version\": 1}").await?;
+    /// TempFile::save_atomic(target.as_path(), b"{\"version\": 2}").await?;
+    /// assert_eq!(tokio::fs::read(&target).await?, b"{\"version\": 2}");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn save_atomic<P: Borrow<Path>>(target: P, data: &[u8]) -> Result<(), Error> {
+        let target = target.borrow();
+        let dir = parent_dir(target);
+
+        // Any failure up to the rename drops the owned file, removing it again.
+        let mut file = Self::new_in(dir).await?;
+        let path = file.file_path().clone();
+        file.write_all(data)
+            .await
+            .map_err(|e| Error::io_at(e, &path))?;
+        file.flush().await.map_err(|e| Error::io_at(e, &path))?;
+        file.file
+            .sync_all()
+            .await
+            .map_err(|e| Error::io_at(e, &path))?;
+
+        file.core
+            .rename(target.to_path_buf())
+            .await
+            .map_err(|e| match e {
+                Error::Io { source, .. } => Error::io_at(source, target),
+                e => e,
+            })?;
+
+        // The file now lives at the target and must not be deleted.
+        drop(file.into_borrowed());
+
+        sync_dir(dir).await.map_err(|e| Error::io_at(e, dir))
+    }
+}
+
+/// Syncs the directory to persist changes to its entries.
+#[cfg(unix)]
+async fn sync_dir(dir: &Path) -> std::io::Result<()> {
+    tokio::fs::File::open(dir).await?.sync_all().await
+}
+
+/// Syncs the directory to persist changes to its entries.
+///
+/// Directories cannot be opened as files on this platform; renames are persisted
+/// by the file system.
+#[cfg(not(unix))]
+async fn sync_dir(_dir: &Path) -> std::io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use super::TempFile;
+use crate::Error;
+use std::borrow::Borrow;
+use std::path::{Path, PathBuf};
+use tokio::fs::File;
+
+impl TempFile {
+    /// Creates a new temporary file in the directory containing an already opened file.
+    ///
+    /// This is useful for atomic writes, where the temporary file must reside on the same
+    /// file system as the file it eventually replaces, but only a handle to that file is
+    /// available. The directory is resolved from the handle via `/proc/self/fd` on Linux
+    /// and Android, and via `F_GETPATH` on macOS and iOS.
+    ///
+    /// ## Platform support
+    ///
+    /// On all other platforms, this method returns [`Error::Unsupported`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `file` - The open file next to which to create the temporary file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let existing = TempFile::new().await?;
+    /// # #[cfg(any(target_os = "linux", target_os = "macos"))]
+    /// # {
+    /// let file = TempFile::new_beside(&existing).await?;
+    /// assert!(file.file_path().parent().is_some());
+    /// # }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn new_beside(file: &File) -> Result<Self, Error> {
+        let path = handle_path(file).await?;
+        match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => Self::new_in(dir).await,
+            _ => Err(Error::InvalidDirectory),
+        }
+    }
+
+    /// Creates a new temporary file in the directory of the specified target path.
+    ///
+    /// This is useful for atomic writes, where the temporary file must reside on the same
+    /// file system as the file it eventually replaces, e.g. via [`TempFile::persist_to`].
+    /// The target itself does not need to exist. If it has no parent, e.g. because it is
+    /// a bare file name, the file is created in the current directory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `target` - The path next to which to create the temporary file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{TempDir, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// # let dir = TempDir::new().await?;
+    /// let target = dir.join("config.toml");
+    ///
+    /// let mut file = TempFile::new_near(target.as_path()).await?;
+    /// assert_eq!(file.file_path().parent(), target.parent());
+    ///
+    /// file.write_all_and_flush(b"answer = 42").await?;
+    /// file.persist_to(target.as_path()).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn new_near<P: Borrow<Path>>(target: P) -> Result<Self, Error> {
+        Self::new_in(parent_dir(target.borrow())).await
+    }
+}
+
+/// Returns the directory containing the specified path, defaulting to the current directory.
+pub(super) fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// Resolves the path of an open file from its handle.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+async fn handle_path(file: &File) -> Result<PathBuf, Error> {
+    use std::os::fd::AsRawFd;
+    let link = format!("/proc/self/fd/{}", file.as_raw_fd());
+    Ok(tokio::fs::read_link(link).await?)
+}
+
+/// Resolves the path of an open file from its handle.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+async fn handle_path(file: &File) -> Result<PathBuf, Error> {
+    use std::ffi::{CStr, OsStr};
+    use std::os::fd::AsRawFd;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut buf = [0 as libc::c_char; libc::PATH_MAX as usize];
+
+    // SAFETY: F_GETPATH writes a null-terminated path of at most MAXPATHLEN (PATH_MAX) bytes.
+    if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETPATH, buf.as_mut_ptr()) } == -1 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    // SAFETY: The buffer was null-terminated by a successful F_GETPATH call.
+    let path = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    Ok(PathBuf::from(OsStr::from_bytes(path.to_bytes())))
+}
+
+/// Resolves the path of an open file from its handle.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+)))]
+async fn handle_path(_file: &File) -> Result<PathBuf, Error> {
+    Err(Error::Unsupported)
+}
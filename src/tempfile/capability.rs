@@ -0,0 +1,120 @@
+use super::{Access, TempFile, TempFileCore, FILE_PREFIX};
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS};
+use crate::open_limit;
+use crate::shared_path::SharedPath;
+use crate::trace;
+use crate::{CreationInfo, Error, Ownership, SharedOwnership};
+use ::cap_std::fs::{Dir, OpenOptions};
+use std::mem::ManuallyDrop;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::fs::File;
+
+impl TempFile {
+    /// Creates a new temporary file in the directory represented by a `cap-std` capability.
+    /// When the instance goes out of scope, the file will be deleted through the capability.
+    ///
+    /// All file system operations on the file, including opening additional handles
+    /// via [`TempFile::open_rw`] and [`TempFile::open_ro`] as well as the final removal,
+    /// are performed relative to the capability. Consequently, [`TempFile::file_path`]
+    /// returns the path of the file relative to the capability directory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dir` - The capability of the directory to create the file in.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use cap_std::{ambient_authority, fs::Dir};
+    /// # let _ = tokio_test::block_on(async {
+    /// let dir = Dir::open_ambient_dir(std::env::temp_dir(), ambient_authority())?;
+    /// let file = TempFile::new_in_cap_dir(&dir).await?;
+    ///
+    /// // The path is relative to the capability.
+    /// assert!(file.file_path().is_relative());
+    /// assert!(dir.exists(file.file_path()));
+    ///
+    /// let path = file.file_path().clone();
+    /// drop(file);
+    /// assert!(!dir.exists(path));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "cap-std")))]
+    pub async fn new_in_cap_dir(dir: &Dir) -> Result<Self, Error> {
+        collision::retry(DEFAULT_ATTEMPTS, || {
+            Self::new_with_name_in_cap_dir(random_name(FILE_PREFIX), dir)
+        })
+        .await
+    }
+
+    /// Creates a new temporary file with the specified name in the directory represented
+    /// by a `cap-std` capability. When the instance goes out of scope, the file will be
+    /// deleted through the capability.
+    ///
+    /// See [`TempFile::new_in_cap_dir`] for details.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - The file name to use.
+    /// * `dir` - The capability of the directory to create the file in.
+    #[cfg_attr(docsrs, doc(cfg(feature = "cap-std")))]
+    pub async fn new_with_name_in_cap_dir<N: AsRef<str>>(
+        name: N,
+        dir: &Dir,
+    ) -> Result<Self, Error> {
+        let dir = Arc::new(dir.try_clone()?);
+        let path = PathBuf::from(name.as_ref());
+
+        let mut options = OpenOptions::new();
+        options.read(true).write(true).create_new(true);
+        let permit = open_limit::acquire().await;
+        let file = open_with(dir.clone(), path.clone(), options).await?;
+
+        let core = TempFileCore {
+            file: ManuallyDrop::new(file.try_clone().await?),
+            ownership: SharedOwnership::new(Ownership::Owned),
+            creation: CreationInfo::new(&path, Ownership::Owned),
+            path: SharedPath::new(path),
+            deleter: Mutex::new(None),
+            flush_on_drop: std::sync::atomic::AtomicBool::new(false),
+            shred_on_drop: std::sync::atomic::AtomicBool::new(false),
+            #[cfg(debug_assertions)]
+            removed: std::sync::atomic::AtomicBool::new(false),
+            #[cfg(all(windows, feature = "windows-ads"))]
+            remove_alternate_streams: std::sync::atomic::AtomicBool::new(false),
+            cap_dir: Some(dir),
+            #[cfg(target_os = "linux")]
+            anonymous: false,
+            process_dir: None,
+            parent: None,
+            permit,
+        };
+        trace::created("file", core.path.get(), Ownership::Owned);
+
+        Ok(Self {
+            file: ManuallyDrop::new(file),
+            core: ManuallyDrop::new(Arc::new(core)),
+        })
+    }
+}
+
+/// Opens an existing file relative to the capability directory.
+pub(super) async fn open(dir: &Arc<Dir>, path: &Path, access: Access) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options
+        .read(true)
+        .write(access.write())
+        .append(access.append());
+    open_with(dir.clone(), path.to_path_buf(), options).await
+}
+
+/// Opens a file relative to the capability directory on the blocking thread pool.
+async fn open_with(dir: Arc<Dir>, path: PathBuf, options: OpenOptions) -> std::io::Result<File> {
+    let file = tokio::task::spawn_blocking(move || dir.open_with(path, &options))
+        .await
+        .map_err(std::io::Error::other)??;
+    Ok(File::from_std(file.into_std()))
+}
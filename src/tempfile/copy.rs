@@ -0,0 +1,99 @@
+use super::TempFile;
+use crate::Error;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+
+impl TempFile {
+    /// Copies the contents of the file to `dest`, returning the number of bytes copied.
+    ///
+    /// Writes pending in this instance are flushed first. The whole file is copied regardless
+    /// of the cursor position, which is left unchanged; the file itself, its path and its
+    /// ownership are not affected. An existing file at `dest` is overwritten, and the
+    /// permissions of the file are copied as well, as with [`tokio::fs::copy`].
+    ///
+    /// ## Platform support
+    ///
+    /// On Linux, the copy is attempted as a reflink via the `FICLONE` ioctl first, sharing
+    /// the data blocks on copy-on-write file systems such as Btrfs or XFS. If the file system
+    /// does not support this, or `dest` is on another file system, the data is copied via
+    /// [`tokio::fs::copy`], which is used on all other platforms.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dest` - The path to copy the file to.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Unsupported`] for anonymous files and files created through a
+    /// `cap-std` capability, as their path cannot be opened directly.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.write_all_and_flush(b"report").await?;
+    ///
+    /// let dest = std::env::temp_dir().join("async-tempfile-copy-to-example.txt");
+    /// assert_eq!(file.copy_to(&dest).await?, 6);
+    /// assert_eq!(tokio::fs::read(&dest).await?, b"report");
+    /// # tokio::fs::remove_file(dest).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn copy_to<P: AsRef<Path>>(&mut self, dest: P) -> Result<u64, Error> {
+        self.core.check_not_removed()?;
+
+        #[cfg(target_os = "linux")]
+        if self.core.anonymous {
+            return Err(Error::Unsupported);
+        }
+
+        // Paths of files created through a capability are relative to it.
+        #[cfg(feature = "cap-std")]
+        if self.core.cap_dir.is_some() {
+            return Err(Error::Unsupported);
+        }
+
+        self.file.flush().await?;
+
+        let from = self.core.path.get().clone();
+        let dest = dest.as_ref();
+
+        #[cfg(target_os = "linux")]
+        {
+            let (from, dest) = (from.clone(), dest.to_path_buf());
+            let cloned = tokio::task::spawn_blocking(move || reflink(&from, &dest))
+                .await
+                .map_err(std::io::Error::other)??;
+            if let Some(len) = cloned {
+                return Ok(len);
+            }
+        }
+
+        Ok(tokio::fs::copy(from, dest).await?)
+    }
+}
+
+/// Clones the file via `FICLONE`, returning `None` if the file system cannot share the data.
+#[cfg(target_os = "linux")]
+fn reflink(from: &Path, dest: &Path) -> std::io::Result<Option<u64>> {
+    use std::os::fd::AsRawFd;
+
+    let source = std::fs::File::open(from)?;
+    let metadata = source.metadata()?;
+    let target = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(dest)?;
+
+    // SAFETY: Both descriptors are valid for the duration of the call.
+    if unsafe { libc::ioctl(target.as_raw_fd(), libc::FICLONE, source.as_raw_fd()) } != 0 {
+        return Ok(None);
+    }
+
+    target.set_permissions(metadata.permissions())?;
+    Ok(Some(metadata.len()))
+}
@@ -0,0 +1,93 @@
+use super::{TempFile, TempFileCore};
+use std::sync::atomic::Ordering;
+use tokio::fs::File;
+#[cfg(feature = "rt-multi-thread")]
+use tokio::io::AsyncWriteExt;
+#[cfg(feature = "rt-multi-thread")]
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+impl TempFile {
+    /// Controls whether pending writes are flushed when a reference to the file is dropped.
+    ///
+    /// By default, dropping a handle does not wait for writes that are still in flight
+    /// (see [Flushing](TempFile#flushing)). If enabled, each handle waits for its pending
+    /// writes when dropped, and the data of a kept file is synced to disk via
+    /// [`File::sync_data`](std::fs::File::sync_data) when the last reference is dropped.
+    /// Files that are deleted on drop are not synced.
+    ///
+    /// This affects all references to the same file. As `Drop` cannot be asynchronous,
+    /// dropping a handle blocks the current thread.
+    ///
+    /// ## Runtime support
+    ///
+    /// Waiting for writes in flight requires the multi-threaded runtime and the `rt-multi-thread`
+    /// feature, as it is performed via `tokio::task::block_in_place`. Otherwise, e.g. on a
+    /// current-thread runtime, writes still in flight when a handle is dropped are completed in
+    /// the background, and syncing the file may precede them; flush explicitly before dropping
+    /// the handle instead.
+    ///
+    /// ## Arguments
+    ///
+    /// * `enabled` - Whether to flush pending writes on drop.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "rt-multi-thread")] {
+    /// # use async_tempfile::TempFile;
+    /// # use tokio::io::AsyncWriteExt;
+    /// # let runtime = tokio::runtime::Builder::new_multi_thread().build()?;
+    /// # runtime.block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.set_flush_on_drop(true);
+    ///
+    /// // Writing does not wait for the data to reach the file.
+    /// file.write_all(b"data").await?;
+    ///
+    /// // Dropping the handle does.
+    /// let path = file.persist()?;
+    /// assert_eq!(std::fs::read(&path)?, b"data");
+    /// # tokio::fs::remove_file(path).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # })?;
+    /// # }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_flush_on_drop(&self, enabled: bool) {
+        self.core.flush_on_drop.store(enabled, Ordering::Release);
+    }
+
+    /// Determines whether pending writes are flushed on drop.
+    /// See [`TempFile::set_flush_on_drop`].
+    pub fn flush_on_drop(&self) -> bool {
+        self.core.flush_on_drop.load(Ordering::Acquire)
+    }
+}
+
+impl TempFileCore {
+    /// Syncs the data of a kept file to disk, if flushing on drop is enabled.
+    pub(super) fn sync_on_drop(&mut self, file: File) {
+        if !*self.flush_on_drop.get_mut() {
+            return;
+        }
+
+        // The handle of the core is never used for I/O, so no operation can be in flight.
+        if let Ok(file) = file.try_into_std() {
+            let _ = file.sync_data();
+        }
+    }
+}
+
+/// Waits for writes in flight on the handle to complete before closing it, if possible.
+pub(super) fn flush_blocking(file: File) {
+    #[cfg(feature = "rt-multi-thread")]
+    if let Ok(handle) = Handle::try_current() {
+        if handle.runtime_flavor() == RuntimeFlavor::MultiThread {
+            let mut file = file;
+            let _ = tokio::task::block_in_place(|| handle.block_on(file.flush()));
+            return;
+        }
+    }
+
+    drop(file);
+}
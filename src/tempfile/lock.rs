@@ -0,0 +1,94 @@
+use super::TempFile;
+use crate::Error;
+use std::fs::TryLockError;
+
+impl TempFile {
+    /// Acquires an exclusive advisory lock on the file, waiting until it becomes available.
+    ///
+    /// The lock is implemented via `flock` on Unix and `LockFileEx` on Windows, called on the
+    /// blocking thread pool so that waiting does not stall the runtime.
+    ///
+    /// ## Advisory locks
+    ///
+    /// The locks are advisory: they only coordinate processes that lock the file as well,
+    /// and do not prevent anyone from reading or writing the file. A lock belongs to the open
+    /// file, i.e. it is shared by the handles obtained via [`TempFile::try_clone`], while
+    /// handles opened separately, e.g. via [`TempFile::open_rw`] or by another process,
+    /// contend for it. A lock is released via [`TempFile::unlock`] or when all handles sharing
+    /// it are closed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// let other = file.open_rw().await?;
+    ///
+    /// file.lock_exclusive().await?;
+    /// assert!(!other.try_lock_exclusive().await?);
+    ///
+    /// file.unlock().await?;
+    /// assert!(other.try_lock_exclusive().await?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn lock_exclusive(&self) -> Result<(), Error> {
+        self.with_std_file(|file| file.lock()).await
+    }
+
+    /// Acquires a shared advisory lock on the file, waiting until it becomes available.
+    ///
+    /// Multiple shared locks may be held at the same time, but not alongside an exclusive lock.
+    /// See [`TempFile::lock_exclusive`] for the semantics of the locks.
+    pub async fn lock_shared(&self) -> Result<(), Error> {
+        self.with_std_file(|file| file.lock_shared()).await
+    }
+
+    /// Attempts to acquire an exclusive advisory lock on the file without waiting.
+    ///
+    /// Returns `false` if the file is locked by another handle.
+    /// See [`TempFile::lock_exclusive`] for the semantics of the locks.
+    pub async fn try_lock_exclusive(&self) -> Result<bool, Error> {
+        self.with_std_file(|file| match file.try_lock() {
+            Ok(()) => Ok(true),
+            Err(TryLockError::WouldBlock) => Ok(false),
+            Err(TryLockError::Error(e)) => Err(e),
+        })
+        .await
+    }
+
+    /// Attempts to acquire a shared advisory lock on the file without waiting.
+    ///
+    /// Returns `false` if the file is exclusively locked by another handle.
+    /// See [`TempFile::lock_exclusive`] for the semantics of the locks.
+    pub async fn try_lock_shared(&self) -> Result<bool, Error> {
+        self.with_std_file(|file| match file.try_lock_shared() {
+            Ok(()) => Ok(true),
+            Err(TryLockError::WouldBlock) => Ok(false),
+            Err(TryLockError::Error(e)) => Err(e),
+        })
+        .await
+    }
+
+    /// Releases the advisory lock held on the file, if any.
+    /// See [`TempFile::lock_exclusive`] for the semantics of the locks.
+    pub async fn unlock(&self) -> Result<(), Error> {
+        self.with_std_file(|file| file.unlock()).await
+    }
+
+    /// Runs a blocking operation on a duplicate of the file handle on the blocking thread pool.
+    pub(super) async fn with_std_file<T, F>(&self, operation: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&std::fs::File) -> std::io::Result<T> + Send + 'static,
+    {
+        self.core.check_not_removed()?;
+        let file = self.file.try_clone().await?.into_std().await;
+        let path = self.core.path.get().clone();
+        tokio::task::spawn_blocking(move || operation(&file))
+            .await
+            .map_err(std::io::Error::other)?
+            .map_err(|e| Error::io_at(e, path))
+    }
+}
@@ -0,0 +1,124 @@
+use super::{TempFile, FILE_PREFIX};
+use crate::collision::{random_name, DEFAULT_ATTEMPTS};
+use crate::Error;
+use std::borrow::Borrow;
+use std::path::Path;
+
+/// Conventional extensions of common MIME types for which the first known extension
+/// in alphabetical order would be unusual, e.g. `jfif` for `image/jpeg`.
+const PREFERRED_EXTENSIONS: &[(&str, &str)] = &[
+    ("application/octet-stream", "bin"),
+    ("application/xml", "xml"),
+    ("audio/mpeg", "mp3"),
+    ("image/jpeg", "jpg"),
+    ("text/html", "html"),
+    ("text/markdown", "md"),
+    ("text/plain", "txt"),
+    ("text/xml", "xml"),
+];
+
+impl TempFile {
+    /// Creates a new temporary file in the default location, using the conventional
+    /// extension of the specified MIME type. When the instance goes out of scope,
+    /// the file will be deleted.
+    ///
+    /// See [`TempFile::new_for_mime_in`] for details.
+    ///
+    /// ## Arguments
+    ///
+    /// * `mime` - The MIME type of the file content, e.g. `image/png`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "mime")))]
+    pub async fn new_for_mime(mime: &str) -> Result<Self, Error> {
+        Self::create_unique(None, DEFAULT_ATTEMPTS, || mime_file_name(mime)).await
+    }
+
+    /// Creates a new temporary file in the specified location, using the conventional
+    /// extension of the specified MIME type. When the instance goes out of scope,
+    /// the file will be deleted.
+    ///
+    /// The extension is looked up via the [`mime_guess`](https://crates.io/crates/mime_guess)
+    /// crate; MIME type parameters such as `; charset=utf-8` are ignored. Files for unknown
+    /// MIME types are created without an extension.
+    ///
+    /// ## Arguments
+    ///
+    /// * `mime` - The MIME type of the file content, e.g. `image/png`.
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new_for_mime_in("image/png", std::env::temp_dir()).await?;
+    /// assert_eq!(file.file_path().extension().unwrap(), "png");
+    ///
+    /// let file = TempFile::new_for_mime_in("application/x-unknown", std::env::temp_dir()).await?;
+    /// assert_eq!(file.file_path().extension(), None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "mime")))]
+    pub async fn new_for_mime_in<P: Borrow<Path>>(mime: &str, dir: P) -> Result<Self, Error> {
+        Self::create_unique(Some(dir.borrow()), DEFAULT_ATTEMPTS, || {
+            mime_file_name(mime)
+        })
+        .await
+    }
+}
+
+/// Generates a random file name with the conventional extension of the MIME type, if known.
+fn mime_file_name(mime: &str) -> String {
+    let name = random_name(FILE_PREFIX);
+    match extension_for_mime(mime) {
+        Some(ext) => format!("{name}.{ext}"),
+        None => name,
+    }
+}
+
+/// Looks up the conventional extension of the MIME type.
+fn extension_for_mime(mime: &str) -> Option<&'static str> {
+    let essence = mime.split(';').next().unwrap_or_default().trim();
+
+    // Wildcards match all extensions, none of which is meaningful.
+    if essence.contains('*') {
+        return None;
+    }
+
+    if let Some((_, ext)) = PREFERRED_EXTENSIONS
+        .iter()
+        .find(|(preferred, _)| preferred.eq_ignore_ascii_case(essence))
+    {
+        return Some(ext);
+    }
+
+    let extensions = mime_guess::get_mime_extensions_str(essence)?;
+
+    // Prefer extensions named like the subtype, e.g. `svg` for `image/svg+xml`.
+    let subtype = essence
+        .split_once('/')
+        .map(|(_, subtype)| subtype.split('+').next().unwrap_or(subtype))
+        .unwrap_or_default();
+    extensions
+        .iter()
+        .find(|ext| ext.eq_ignore_ascii_case(subtype))
+        .or(extensions.first())
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_for_mime() {
+        assert_eq!(extension_for_mime("image/png"), Some("png"));
+        assert_eq!(extension_for_mime("image/jpeg"), Some("jpg"));
+        assert_eq!(extension_for_mime("image/svg+xml"), Some("svg"));
+        assert_eq!(extension_for_mime("Text/Plain; charset=utf-8"), Some("txt"));
+        assert_eq!(extension_for_mime("application/json"), Some("json"));
+        assert_eq!(extension_for_mime("application/x-unknown"), None);
+        assert_eq!(extension_for_mime("*/*"), None);
+        assert_eq!(extension_for_mime("invalid"), None);
+    }
+}
@@ -0,0 +1,88 @@
+use super::TempFile;
+use crate::Error;
+use memmap2::{Mmap, MmapMut};
+
+impl TempFile {
+    /// Maps the contents of the file into memory for reading.
+    ///
+    /// The mapping covers the length of the file at the time of the call. Data written
+    /// through [`AsyncWrite`](tokio::io::AsyncWrite) should be flushed before mapping the file.
+    ///
+    /// ## Safety
+    ///
+    /// As with [`Mmap::map`], the behavior is undefined if the mapped file is modified or
+    /// truncated while the mapping is alive, be it through this crate, other handles or
+    /// other processes.
+    ///
+    /// The mapping should be dropped before the last reference to an owned file is dropped:
+    /// on Windows, a mapped file cannot be deleted and would be left behind, while on Unix,
+    /// the mapping remains readable after the file was deleted, keeping its disk space in use
+    /// until the mapping is dropped.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if the file could not be mapped.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.write_all_and_flush(b"data").await?;
+    ///
+    /// // SAFETY: The file is not modified while it is mapped.
+    /// let map = unsafe { file.mmap()? };
+    /// assert_eq!(&map[..], b"data");
+    /// drop(map);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub unsafe fn mmap(&self) -> Result<Mmap, Error> {
+        self.core.check_not_removed()?;
+        // SAFETY: Upheld by the caller.
+        unsafe { Mmap::map(&*self.file) }.map_err(|e| Error::io_at(e, self.core.path.get().clone()))
+    }
+
+    /// Maps the contents of the file into memory for reading and writing.
+    ///
+    /// Writes to the mapping are carried through to the file; call [`MmapMut::flush`]
+    /// to ensure they reached the disk. The handle must have been opened for writing,
+    /// e.g. not via [`TempFile::open_ro`].
+    ///
+    /// ## Safety
+    ///
+    /// The same requirements as for [`TempFile::mmap`] apply.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if the file could not be mapped, e.g. because the handle
+    /// is read-only.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.set_len(4).await?;
+    ///
+    /// // SAFETY: The file is not modified otherwise while it is mapped.
+    /// let mut map = unsafe { file.mmap_mut()? };
+    /// map.copy_from_slice(b"data");
+    /// map.flush()?;
+    /// drop(map);
+    ///
+    /// assert_eq!(file.read_all().await?, b"data");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub unsafe fn mmap_mut(&self) -> Result<MmapMut, Error> {
+        self.core.check_not_removed()?;
+        // SAFETY: Upheld by the caller.
+        unsafe { MmapMut::map_mut(&*self.file) }
+            .map_err(|e| Error::io_at(e, self.core.path.get().clone()))
+    }
+}
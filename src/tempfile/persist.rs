@@ -0,0 +1,153 @@
+use super::TempFile;
+use crate::{Error, Ownership};
+use std::borrow::Borrow;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+
+impl TempFile {
+    /// Flushes the file and moves it to the specified destination, replacing any file
+    /// that already exists there. The file is kept when the last reference is dropped.
+    ///
+    /// The file is renamed, so readers of the destination observe either the previous file
+    /// or the complete new one. If the destination is on a different file system, the data
+    /// is copied to a temporary file next to the destination, which is then renamed over it,
+    /// and the original file is removed.
+    ///
+    /// Like [`TempFile::persist`], this affects all references to the same file, which
+    /// report the new path afterwards. Handles obtained via [`TempFile::open_rw`],
+    /// [`TempFile::open_ro`] and [`TempFile::try_clone`] stay valid; after a copy, however,
+    /// they refer to the removed original.
+    ///
+    /// The file is not synced to disk. For crash safety, call [`TempFile::sync_all`]
+    /// before persisting the file, so that the destination never refers to incomplete data.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dest` - The path to move the file to.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Unsupported`] for anonymous files and files created through a
+    /// `cap-std` directory capability. If the file could not be moved, it is dropped and,
+    /// if this was the last reference to an owned file, deleted.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{TempDir, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// # let dir = TempDir::new().await?;
+    /// let dest = dir.join("report.csv");
+    ///
+    /// let mut file = TempFile::new_in(dir.dir_path().as_path()).await?;
+    /// file.write_all_and_flush(b"a,b,c").await?;
+    /// file.persist_to(dest.as_path()).await?;
+    /// assert_eq!(tokio::fs::read(&dest).await?, b"a,b,c");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn persist_to<P: Borrow<Path>>(self, dest: P) -> Result<(), Error> {
+        self.persist_with(dest.borrow(), false).await
+    }
+
+    /// Flushes the file and moves it to the specified destination, failing if a file
+    /// already exists there. The file is kept when the last reference is dropped.
+    ///
+    /// The file is hard-linked to the destination, which fails atomically if the destination
+    /// exists, and the original name is removed. If the destination is on a different file
+    /// system, the data is copied to a temporary file next to the destination instead, which
+    /// is then linked to the destination. The file system of the destination must therefore
+    /// support hard links. See [`TempFile::persist_to`] for the effects on other references.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dest` - The path to move the file to.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] with [`std::io::ErrorKind::AlreadyExists`] if the destination
+    /// exists, and [`Error::Unsupported`] for anonymous files and files created through a
+    /// `cap-std` directory capability. If the file could not be moved, it is dropped and,
+    /// if this was the last reference to an owned file, deleted.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::{TempDir, TempFile};
+    /// # let _ = tokio_test::block_on(async {
+    /// # let dir = TempDir::new().await?;
+    /// let dest = dir.join("report.csv");
+    ///
+    /// let file = TempFile::from_bytes_in(b"first", dir.dir_path().as_path()).await?;
+    /// file.persist_to_noclobber(dest.as_path()).await?;
+    ///
+    /// let file = TempFile::from_bytes_in(b"second", dir.dir_path().as_path()).await?;
+    /// assert!(file.persist_to_noclobber(dest.as_path()).await.is_err());
+    /// assert_eq!(tokio::fs::read(&dest).await?, b"first");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn persist_to_noclobber<P: Borrow<Path>>(self, dest: P) -> Result<(), Error> {
+        self.persist_with(dest.borrow(), true).await
+    }
+
+    async fn persist_with(mut self, dest: &Path, noclobber: bool) -> Result<(), Error> {
+        self.core.check_not_removed()?;
+
+        #[cfg(target_os = "linux")]
+        if self.core.anonymous {
+            return Err(Error::Unsupported);
+        }
+
+        // Paths of files created through a capability are relative to it.
+        #[cfg(feature = "cap-std")]
+        if self.core.cap_dir.is_some() {
+            return Err(Error::Unsupported);
+        }
+
+        self.file.flush().await?;
+
+        let from = self.core.path.get().clone();
+        let moved = if noclobber {
+            tokio::fs::hard_link(&from, dest).await
+        } else {
+            tokio::fs::rename(&from, dest).await
+        };
+
+        let original_remains = match moved {
+            Ok(()) => noclobber,
+            Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                copy_to(&from, dest, noclobber).await?;
+                true
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        // The original name is still in place after linking or copying the file.
+        // Should its removal fail, the file is deleted on drop as usual.
+        if original_remains {
+            tokio::fs::remove_file(&from).await?;
+        }
+
+        self.core.path.set(dest.to_path_buf());
+        self.core.ownership.set(Ownership::Borrowed);
+        Ok(())
+    }
+}
+
+/// Copies the file to a temporary file next to the destination and moves it into place.
+async fn copy_to(from: &Path, dest: &Path, noclobber: bool) -> Result<(), Error> {
+    // The staged file is removed on drop unless it was renamed into place.
+    let mut staged = TempFile::new_near(dest).await?;
+    let mut source = tokio::fs::File::open(from).await?;
+    staged.fill_from(&mut source).await?;
+
+    if noclobber {
+        tokio::fs::hard_link(staged.file_path(), dest).await?;
+    } else {
+        staged.core.rename(dest.to_path_buf()).await?;
+        drop(staged.into_borrowed());
+    }
+
+    Ok(())
+}
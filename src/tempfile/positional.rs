@@ -0,0 +1,244 @@
+use super::TempFile;
+use crate::Error;
+use std::io::{IoSlice, IoSliceMut};
+
+impl TempFile {
+    /// Reads from the file at the specified offset into the provided buffers,
+    /// filling them in order. Returns the total number of bytes read, which may
+    /// be less than the combined size of the buffers when reaching the end of the file.
+    ///
+    /// This uses `preadv` where available and sequenced positional reads otherwise.
+    /// The read is performed on a duplicate of the file handle on the blocking thread pool,
+    /// into an intermediate buffer that is copied into the provided buffers afterwards.
+    ///
+    /// ## Cursor behavior
+    ///
+    /// On Unix platforms the file cursor is not affected, so the method can be used
+    /// concurrently with other handles sharing the same file description
+    /// (see [`TempFile::try_clone`]). On Windows, the cursor shared by this handle and its
+    /// duplicates is moved to the end of the read range as a side effect; use a handle with
+    /// an independent cursor (see [`TempFile::open_rw`]) if the position matters.
+    ///
+    /// Data written through [`AsyncWrite`](tokio::io::AsyncWrite) should be flushed
+    /// before reading it back positionally.
+    ///
+    /// ## Arguments
+    ///
+    /// * `bufs` - The buffers to read into.
+    /// * `offset` - The position in the file to start reading at.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use std::io::{IoSlice, IoSliceMut};
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// file.write_vectored_at(&[IoSlice::new(b"hello"), IoSlice::new(b"world")], 2)
+    ///     .await?;
+    ///
+    /// let (mut first, mut second) = ([0u8; 3], [0u8; 4]);
+    /// let read = file
+    ///     .read_vectored_at(
+    ///         &mut [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)],
+    ///         4,
+    ///     )
+    ///     .await?;
+    /// assert_eq!(read, 7);
+    /// assert_eq!(&first, b"llo");
+    /// assert_eq!(&second, b"worl");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn read_vectored_at(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        offset: u64,
+    ) -> Result<usize, Error> {
+        let lens = bufs.iter().map(|buf| buf.len()).collect::<Vec<_>>();
+        let (read, data) = self
+            .with_std_file(move |file| {
+                let mut data = vec![0; lens.iter().sum()];
+                let mut slices = Vec::with_capacity(lens.len());
+                let mut rest = data.as_mut_slice();
+                for len in lens {
+                    let (buf, tail) = std::mem::take(&mut rest).split_at_mut(len);
+                    slices.push(IoSliceMut::new(buf));
+                    rest = tail;
+                }
+
+                let read = imp::read_vectored_at(file, &mut slices, offset)?;
+                Ok((read, data))
+            })
+            .await?;
+
+        let mut data = &data[..read];
+        for buf in bufs.iter_mut() {
+            let (head, tail) = data.split_at(buf.len().min(data.len()));
+            buf[..head.len()].copy_from_slice(head);
+            data = tail;
+        }
+        Ok(read)
+    }
+
+    /// Writes the provided buffers to the file at the specified offset, in order.
+    /// Returns the total number of bytes written, which may be less than the combined
+    /// size of the buffers.
+    ///
+    /// This uses `pwritev` where available and sequenced positional writes otherwise.
+    /// The buffers are copied, and the write is performed on a duplicate of the file handle
+    /// on the blocking thread pool.
+    ///
+    /// ## Cursor behavior
+    ///
+    /// On Unix platforms the file cursor is not affected, so the method can be used
+    /// concurrently with other handles sharing the same file description
+    /// (see [`TempFile::try_clone`]). On Windows, the cursor shared by this handle and its
+    /// duplicates is moved to the end of the written range as a side effect; use a handle with
+    /// an independent cursor (see [`TempFile::open_rw`]) if the position matters.
+    ///
+    /// ## Arguments
+    ///
+    /// * `bufs` - The buffers to write.
+    /// * `offset` - The position in the file to start writing at.
+    pub async fn write_vectored_at(
+        &self,
+        bufs: &[IoSlice<'_>],
+        offset: u64,
+    ) -> Result<usize, Error> {
+        let data = bufs.iter().map(|buf| buf.to_vec()).collect::<Vec<_>>();
+        self.with_std_file(move |file| {
+            let slices = data.iter().map(|buf| IoSlice::new(buf)).collect::<Vec<_>>();
+            imp::write_vectored_at(file, &slices, offset)
+        })
+        .await
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "dragonfly"
+))]
+mod imp {
+    use std::fs::File;
+    use std::io::{IoSlice, IoSliceMut};
+    use std::os::fd::AsRawFd;
+
+    /// The maximum number of buffers accepted by a single `preadv`/`pwritev` call.
+    fn iov_count(len: usize) -> libc::c_int {
+        len.min(libc::c_int::MAX as usize) as libc::c_int
+    }
+
+    pub fn read_vectored_at(
+        file: &File,
+        bufs: &mut [IoSliceMut<'_>],
+        offset: u64,
+    ) -> std::io::Result<usize> {
+        // SAFETY: IoSliceMut is guaranteed to be ABI compatible with iovec on Unix.
+        let read = unsafe {
+            libc::preadv(
+                file.as_raw_fd(),
+                bufs.as_mut_ptr() as *const libc::iovec,
+                iov_count(bufs.len()),
+                offset as libc::off_t,
+            )
+        };
+        if read < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(read as usize)
+    }
+
+    pub fn write_vectored_at(
+        file: &File,
+        bufs: &[IoSlice<'_>],
+        offset: u64,
+    ) -> std::io::Result<usize> {
+        // SAFETY: IoSlice is guaranteed to be ABI compatible with iovec on Unix.
+        let written = unsafe {
+            libc::pwritev(
+                file.as_raw_fd(),
+                bufs.as_ptr() as *const libc::iovec,
+                iov_count(bufs.len()),
+                offset as libc::off_t,
+            )
+        };
+        if written < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(written as usize)
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "dragonfly"
+)))]
+mod imp {
+    use std::fs::File;
+    use std::io::{IoSlice, IoSliceMut};
+
+    #[cfg(unix)]
+    fn read_at(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        std::os::unix::fs::FileExt::read_at(file, buf, offset)
+    }
+
+    #[cfg(windows)]
+    fn read_at(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+    }
+
+    #[cfg(unix)]
+    fn write_at(file: &File, buf: &[u8], offset: u64) -> std::io::Result<usize> {
+        std::os::unix::fs::FileExt::write_at(file, buf, offset)
+    }
+
+    #[cfg(windows)]
+    fn write_at(file: &File, buf: &[u8], offset: u64) -> std::io::Result<usize> {
+        std::os::windows::fs::FileExt::seek_write(file, buf, offset)
+    }
+
+    pub fn read_vectored_at(
+        file: &File,
+        bufs: &mut [IoSliceMut<'_>],
+        offset: u64,
+    ) -> std::io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs.iter_mut() {
+            let read = read_at(file, buf, offset + total as u64)?;
+            total += read;
+            if read < buf.len() {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    pub fn write_vectored_at(
+        file: &File,
+        bufs: &[IoSlice<'_>],
+        offset: u64,
+    ) -> std::io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs.iter() {
+            let written = write_at(file, buf, offset + total as u64)?;
+            total += written;
+            if written < buf.len() {
+                break;
+            }
+        }
+        Ok(total)
+    }
+}
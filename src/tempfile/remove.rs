@@ -0,0 +1,125 @@
+use super::{shred, TempFile};
+use crate::{Error, Ownership};
+use std::mem::ManuallyDrop;
+use std::sync::Arc;
+
+impl TempFile {
+    /// Removes the file right away instead of when the last reference to it is dropped,
+    /// reporting any error that occurs.
+    ///
+    /// If a custom deleter was set via [`TempFile::with_deleter`], it is called instead.
+    /// If shredding is enabled via [`TempFile::set_shred_on_drop`], the file is shredded before
+    /// it is removed, as on drop. Borrowed files are kept, in which case this only drops the
+    /// reference.
+    ///
+    /// Other references to the file, e.g. obtained via [`TempFile::try_clone`], stay valid
+    /// but refer to a removed file. In debug builds, operations of this crate on these
+    /// references, such as [`TempFile::open_ro`], fail with [`Error::FileVanished`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if the file could not be removed; it is then still deleted
+    /// when the last reference to it is dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// let path = file.file_path().clone();
+    /// file.remove().await?;
+    /// assert!(!path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn remove(self) -> Result<(), Error> {
+        if self.core.ownership.release() != Ownership::Owned {
+            return Ok(());
+        }
+
+        let core = Arc::clone(&self.core);
+        let result = tokio::task::spawn_blocking(move || {
+            if let Some(file) = core.shred_handle(&core.file) {
+                shred::shred_and_close(file);
+            }
+            core.delete()
+        })
+        .await
+        .map_err(std::io::Error::other)?;
+
+        if let Err(e) = result {
+            self.core.ownership.set(Ownership::Owned);
+            return Err(e.into());
+        }
+
+        #[cfg(debug_assertions)]
+        self.core
+            .removed
+            .store(true, std::sync::atomic::Ordering::Release);
+        Ok(())
+    }
+
+    /// Closes this reference to the file and, if it was the last reference to an owned file,
+    /// deletes the file, reporting any error that occurs.
+    ///
+    /// Unlike dropping the instance, the deletion is performed on the blocking thread pool and
+    /// failures are reported. If a custom deleter was set via [`TempFile::with_deleter`], it is
+    /// called instead. Unlike [`TempFile::remove`], the file is kept if other references to it
+    /// remain, in which case this does nothing and succeeds; the file is then deleted along
+    /// with the last reference, as usual. Borrowed files are never deleted, so closing them
+    /// succeeds as well.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if the file could not be deleted. The file is not deleted again
+    /// afterwards.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// let path = file.file_path().clone();
+    ///
+    /// let clone = file.open_ro().await?;
+    /// clone.close().await?;
+    /// assert!(path.exists());
+    ///
+    /// file.close().await?;
+    /// assert!(!path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[doc(alias = "drop_async")]
+    pub async fn close(self) -> Result<(), Error> {
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used again and its Drop implementation does not run.
+        let core = unsafe {
+            ManuallyDrop::drop(&mut this.file);
+            ManuallyDrop::take(&mut this.core)
+        };
+
+        let core = match Arc::try_unwrap(core) {
+            Ok(core) => core,
+            Err(_) => return Ok(()),
+        };
+
+        // The file is deleted here rather than when dropping the core.
+        if core.ownership.release() != Ownership::Owned {
+            return Ok(());
+        }
+
+        tokio::task::spawn_blocking(move || {
+            if let Some(file) = core.shred_handle(&core.file) {
+                shred::shred_and_close(file);
+            }
+            core.delete()
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        Ok(())
+    }
+}
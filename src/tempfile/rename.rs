@@ -0,0 +1,127 @@
+use super::{TempFile, TempFileCore};
+use crate::file_name::is_valid_file_name;
+use crate::Error;
+use std::ffi::OsStr;
+use std::path::{is_separator, PathBuf};
+
+impl TempFile {
+    /// Changes the extension of the file, keeping its stem and directory.
+    ///
+    /// The file is renamed on the file system and the new path is visible to all references
+    /// to the file, including handles obtained via [`TempFile::open_rw`], [`TempFile::open_ro`]
+    /// and [`TempFile::try_clone`]. Open handles stay valid. An empty extension removes the
+    /// current extension. See [`Path::with_extension`](std::path::Path::with_extension)
+    /// for details.
+    ///
+    /// Concurrent renames of the same file through different references are not synchronized.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ext` - The new extension, without the leading dot.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the extension contains path separators or the
+    /// resulting file name is invalid.
+    /// Returns [`Error::Unsupported`] for anonymous files.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new_with_name("report.tmp").await?;
+    /// file.set_extension("json").await?;
+    /// assert!(file.file_path().ends_with("report.json"));
+    /// assert!(file.file_path().is_file());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn set_extension<S: AsRef<str>>(&self, ext: S) -> Result<(), Error> {
+        let ext = ext.as_ref();
+        if ext.chars().any(is_separator) {
+            return Err(Error::InvalidFile);
+        }
+
+        let to = self.core.path.get().with_extension(ext);
+        match to.file_name() {
+            Some(name) if is_valid_file_name(name) => {}
+            _ => return Err(Error::InvalidFile),
+        }
+
+        self.core.rename(to).await
+    }
+
+    /// Changes the name of the file, keeping it in its current directory.
+    ///
+    /// Like with [`TempFile::set_extension`], the file is renamed on the file system and the
+    /// new path is visible to all references to the file; an owned file is deleted under its
+    /// new name. An existing file with the new name is replaced, as with
+    /// [`tokio::fs::rename`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `new_name` - The new file name.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidFile`] if the name is not a valid file name, e.g. because it
+    /// contains path separators.
+    /// Returns [`Error::Unsupported`] for anonymous files.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// file.rename("upload.bin").await?;
+    /// assert!(file.file_path().ends_with("upload.bin"));
+    /// assert!(file.file_path().is_file());
+    ///
+    /// let path = file.file_path().clone();
+    /// drop(file);
+    /// assert!(!path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[doc(alias = "rename_within_dir")]
+    pub async fn rename<N: AsRef<str>>(&self, new_name: N) -> Result<(), Error> {
+        let new_name = new_name.as_ref();
+        if !is_valid_file_name(OsStr::new(new_name)) {
+            return Err(Error::InvalidFile);
+        }
+
+        let to = self.core.path.get().with_file_name(new_name);
+        self.core.rename(to).await
+    }
+}
+
+impl TempFileCore {
+    /// Renames the file and updates the shared path.
+    pub(super) async fn rename(&self, to: PathBuf) -> Result<(), Error> {
+        self.check_not_removed()?;
+
+        // Anonymous files have no name to change.
+        #[cfg(target_os = "linux")]
+        if self.anonymous {
+            return Err(Error::Unsupported);
+        }
+
+        let from = self.path.get();
+
+        #[cfg(feature = "cap-std")]
+        if let Some(dir) = &self.cap_dir {
+            let (dir, from, target) = (dir.clone(), from.clone(), to.clone());
+            tokio::task::spawn_blocking(move || dir.rename(from, &dir, target))
+                .await
+                .map_err(std::io::Error::other)??;
+            self.path.set(to);
+            return Ok(());
+        }
+
+        tokio::fs::rename(from, &to).await?;
+        self.path.set(to);
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use super::{describe_missing_directory, TempFile, FILE_PREFIX};
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS};
+use crate::open_limit;
+use crate::{Error, TempPath};
+use std::borrow::Borrow;
+use std::path::{Path, PathBuf};
+use tokio::fs::OpenOptions;
+
+impl TempFile {
+    /// Reserves a unique file name in the specified directory without keeping the file open,
+    /// returning a cleanup guard along with the reserved path.
+    ///
+    /// This is intended for handing a path to another process that creates the file itself.
+    /// Whatever exists at the path when the [`TempPath`] guard is dropped is removed.
+    ///
+    /// Uniqueness is verified by exclusively creating an empty placeholder file, which
+    /// is removed again before returning so that the path can be created with `O_EXCL`
+    /// semantics. Another process could take the name in the meantime, although this
+    /// is highly unlikely given the randomness of the name.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dir` - The directory to reserve the name in.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let (guard, path) = TempFile::reserve_name_in(std::env::temp_dir()).await?;
+    /// assert!(!path.exists());
+    ///
+    /// // E.g. a subprocess creates the file.
+    /// tokio::fs::write(&path, b"output").await?;
+    ///
+    /// drop(guard);
+    /// assert!(!path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn reserve_name_in<P: Borrow<Path>>(dir: P) -> Result<(TempPath, PathBuf), Error> {
+        let path = create_placeholder(dir.borrow()).await?;
+        tokio::fs::remove_file(&path).await?;
+
+        Ok((TempPath::guard(path.clone()), path))
+    }
+}
+
+impl TempPath {
+    /// Creates an empty file with a unique name in the default location and closes it again,
+    /// returning a guard that deletes the file when dropped.
+    ///
+    /// See [`TempPath::new_in`] for details.
+    pub async fn new() -> Result<Self, Error> {
+        Self::new_in(TempFile::default_dir()).await
+    }
+
+    /// Creates an empty file with a unique name in the specified directory and closes it again,
+    /// returning a guard that deletes the file when dropped.
+    ///
+    /// Unlike [`TempFile::reserve_name_in`], the file is kept, so that the name stays reserved
+    /// until the guard is dropped. This is intended for handing the path to code that opens
+    /// the existing file itself, e.g. a subprocess, without keeping a handle open.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempPath;
+    /// # let _ = tokio_test::block_on(async {
+    /// let path = TempPath::new_in(std::env::temp_dir()).await?;
+    /// assert!(path.is_file());
+    ///
+    /// // E.g. a subprocess writes to the file.
+    /// tokio::fs::write(&path, b"output").await?;
+    ///
+    /// let file_path = path.path().to_path_buf();
+    /// drop(path);
+    /// assert!(!file_path.exists());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn new_in<P: Borrow<Path>>(dir: P) -> Result<Self, Error> {
+        Ok(Self::guard(create_placeholder(dir.borrow()).await?))
+    }
+}
+
+/// Exclusively creates an empty file with a unique name in the directory and closes it again.
+async fn create_placeholder(dir: &Path) -> Result<PathBuf, Error> {
+    collision::retry(DEFAULT_ATTEMPTS, || async {
+        let path = dir.join(random_name(FILE_PREFIX));
+        let _permit = open_limit::acquire().await;
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+        {
+            Ok(_) => Ok(path),
+            Err(e) => Err(describe_missing_directory(e, &path, dir).await),
+        }
+    })
+    .await
+}
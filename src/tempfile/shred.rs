@@ -0,0 +1,112 @@
+use super::{duplicate, TempFile, TempFileCore};
+use std::io::{Seek, SeekFrom, Write};
+use std::sync::atomic::Ordering;
+use tokio::fs::File;
+
+/// The size of the blocks of zeros written when shredding a file.
+const BLOCK_SIZE: usize = 64 * 1024;
+
+impl TempFile {
+    /// Controls whether the contents of the file are overwritten with zeros before it is
+    /// deleted on drop, e.g. for files holding secrets.
+    ///
+    /// If enabled, the file is overwritten in its full length, as read from its metadata,
+    /// and synced to disk before it is deleted along with the last reference, be it on drop
+    /// or via [`TempFile::close`]. Files removed via [`TempFile::remove`] are shredded right
+    /// away, even while other references remain. Borrowed files are not shredded.
+    /// This affects all references to the same file. As `Drop` cannot be asynchronous,
+    /// the data is overwritten on the current thread along with the deletion of the file;
+    /// with the `rt-multi-thread` feature, a worker thread of a multi-threaded runtime hands
+    /// its other tasks off to another thread meanwhile.
+    ///
+    /// **Note:** This is a best-effort measure. On copy-on-write and journaling file systems,
+    /// SSDs with wear leveling, or with snapshots and backups, copies of the original data
+    /// may remain on the storage device. Files opened without write access are not shredded.
+    ///
+    /// ## Arguments
+    ///
+    /// * `enabled` - Whether to shred the file on drop.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let mut file = TempFile::new().await?;
+    /// file.set_shred_on_drop(true);
+    /// file.write_all_and_flush(b"secret").await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn set_shred_on_drop(&self, enabled: bool) {
+        self.core.shred_on_drop.store(enabled, Ordering::Release);
+    }
+
+    /// Determines whether the file is shredded on drop. See [`TempFile::set_shred_on_drop`].
+    pub fn shred_on_drop(&self) -> bool {
+        self.core.shred_on_drop.load(Ordering::Acquire)
+    }
+}
+
+impl TempFileCore {
+    /// Duplicates the handle of the file for overwriting its contents before it is deleted,
+    /// if shredding on drop is enabled.
+    pub(super) fn shred_handle(&self, file: &File) -> Option<std::fs::File> {
+        if !self.shred_on_drop.load(Ordering::Acquire) {
+            return None;
+        }
+
+        duplicate(file).ok()
+    }
+}
+
+/// Overwrites the contents of the file with zeros and closes the handle.
+pub(super) fn shred_and_close(mut file: std::fs::File) {
+    let _ = shred(&mut file);
+}
+
+/// Overwrites the file in its current length with zeros and syncs it to disk.
+fn shred(file: &mut std::fs::File) -> std::io::Result<()> {
+    let mut remaining = file.metadata()?.len();
+    file.seek(SeekFrom::Start(0))?;
+
+    let zeros = [0u8; BLOCK_SIZE];
+    while remaining > 0 {
+        let len = remaining.min(BLOCK_SIZE as u64) as usize;
+        file.write_all(&zeros[..len])?;
+        remaining -= len as u64;
+    }
+
+    file.sync_data()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_shred() {
+        let path = std::env::temp_dir().join(format!("atmp_shred_{}", std::process::id()));
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+        let secret = vec![0xAAu8; BLOCK_SIZE + 17];
+        file.write_all(&secret).unwrap();
+
+        shred(&mut file).unwrap();
+
+        let mut content = Vec::new();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        file.read_to_end(&mut content).unwrap();
+        drop(file);
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(content.len(), secret.len());
+        assert!(content.iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,246 @@
+use super::TempFile;
+use crate::Error;
+
+impl TempFile {
+    /// Reserves disk space for the file, so that it can grow to the specified size
+    /// without running out of space.
+    ///
+    /// The file is grown to at least `bytes` bytes; it is never shrunk. If the file system
+    /// does not have enough free space, [`Error::OutOfSpace`] is returned and the file
+    /// is left unchanged, rather than growing it partially.
+    ///
+    /// ## Platform support
+    ///
+    /// * On Linux and Android, the space is allocated via `fallocate`, guaranteeing that
+    ///   subsequent writes within the reserved range do not fail for lack of space.
+    /// * On macOS and iOS, the space is allocated via `fcntl` with `F_PREALLOCATE`, preferring
+    ///   contiguous space, before extending the file to the requested size.
+    /// * On Windows, extending the file allocates the space as well.
+    /// * On other Unix platforms, and on Linux, Android, macOS and iOS if the file system does
+    ///   not support allocating space, the free space is checked before extending the file via
+    ///   [`File::set_len`](tokio::fs::File::set_len). The file may be sparse, so the space
+    ///   is not actually allocated and writes may still run out of space if the file system
+    ///   is filled concurrently.
+    /// * On all other platforms, the file is extended without checking the free space.
+    ///
+    /// ## Arguments
+    ///
+    /// * `bytes` - The size to reserve, in bytes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// file.try_reserve_space(4096).await?;
+    /// assert_eq!(file.metadata().await?.len(), 4096);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[doc(alias = "fallocate")]
+    pub async fn try_reserve_space(&self, bytes: u64) -> Result<(), Error> {
+        self.with_std_file(move |file| imp::allocate(file, bytes, check_and_extend))
+            .await
+    }
+
+    /// Preallocates disk space for the file and grows it to the specified size, e.g. when
+    /// the final size of the file is known up front, to avoid fragmentation.
+    ///
+    /// Like [`TempFile::try_reserve_space`], this allocates the blocks via `fallocate` on Linux
+    /// and Android and `F_PREALLOCATE` on macOS and iOS, and by extending the file on Windows,
+    /// and never shrinks the file. Unlike it, this does not check the free space if the
+    /// platform or file system does not support allocating space, but merely extends the file
+    /// via [`File::set_len`](tokio::fs::File::set_len), which may leave it sparse.
+    ///
+    /// ## Arguments
+    ///
+    /// * `size` - The size to grow the file to, in bytes.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::OutOfSpace`] if the space could not be allocated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// file.preallocate(1 << 16).await?;
+    /// assert_eq!(file.metadata().await?.len(), 1 << 16);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn preallocate(&self, size: u64) -> Result<(), Error> {
+        self.with_std_file(move |file| imp::allocate(file, size, extend))
+            .await
+    }
+}
+
+/// Grows the file if the file system does not support allocating space.
+type Unsupported = fn(&std::fs::File, u64) -> std::io::Result<()>;
+
+/// Extends the file to the specified size without allocating space, unless it is larger.
+fn extend(file: &std::fs::File, bytes: u64) -> std::io::Result<()> {
+    if file.metadata()?.len() < bytes {
+        file.set_len(bytes)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+use extend as check_and_extend;
+/// Checks the available space before extending the file, where possible.
+#[cfg(unix)]
+use fallback::reserve as check_and_extend;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod imp {
+    use super::Unsupported;
+    use std::fs::File;
+    use std::os::fd::AsRawFd;
+
+    pub fn allocate(file: &File, bytes: u64, unsupported: Unsupported) -> std::io::Result<()> {
+        let len = libc::off_t::try_from(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+        // SAFETY: The file descriptor is valid for the lifetime of `file`.
+        if unsafe { libc::fallocate(file.as_raw_fd(), 0, 0, len) } == 0 {
+            return Ok(());
+        }
+
+        let error = std::io::Error::last_os_error();
+        if error.raw_os_error() == Some(libc::EOPNOTSUPP) {
+            // The file system does not support allocating space.
+            return unsupported(file, bytes);
+        }
+
+        Err(error)
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::Unsupported;
+    use std::fs::File;
+
+    pub fn allocate(file: &File, bytes: u64, _unsupported: Unsupported) -> std::io::Result<()> {
+        if file.metadata()?.len() >= bytes {
+            return Ok(());
+        }
+
+        // Extending the file allocates the space; this fails without changing the file
+        // if the disk is full.
+        file.set_len(bytes)
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod imp {
+    use super::Unsupported;
+    use std::fs::File;
+    use std::os::fd::AsRawFd;
+
+    pub fn allocate(file: &File, bytes: u64, unsupported: Unsupported) -> std::io::Result<()> {
+        let len = file.metadata()?.len();
+        if len >= bytes {
+            return Ok(());
+        }
+
+        let length = libc::off_t::try_from(bytes - len)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let mut store = libc::fstore_t {
+            fst_flags: libc::F_ALLOCATECONTIG | libc::F_ALLOCATEALL,
+            fst_posmode: libc::F_PEOFPOSMODE,
+            fst_offset: 0,
+            fst_length: length,
+            fst_bytesalloc: 0,
+        };
+
+        // SAFETY: The file descriptor is valid and `store` is a valid pointer.
+        if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_PREALLOCATE, &mut store) } == -1 {
+            // Contiguous space may not be available; retry allowing fragmented allocation.
+            store.fst_flags = libc::F_ALLOCATEALL;
+
+            // SAFETY: As above.
+            if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_PREALLOCATE, &mut store) } == -1 {
+                let error = std::io::Error::last_os_error();
+                if error.raw_os_error() == Some(libc::ENOTSUP) {
+                    // The file system does not support allocating space.
+                    return unsupported(file, bytes);
+                }
+                return Err(error);
+            }
+        }
+
+        // The allocation does not change the length of the file.
+        file.set_len(bytes)
+    }
+}
+
+#[cfg(all(
+    unix,
+    not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios"
+    ))
+))]
+mod imp {
+    use super::Unsupported;
+    use std::fs::File;
+
+    pub fn allocate(file: &File, bytes: u64, unsupported: Unsupported) -> std::io::Result<()> {
+        // Allocating space is not supported.
+        unsupported(file, bytes)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use super::Unsupported;
+    use std::fs::File;
+
+    pub fn allocate(file: &File, bytes: u64, _unsupported: Unsupported) -> std::io::Result<()> {
+        super::extend(file, bytes)
+    }
+}
+
+/// Checks the available space before extending the file.
+#[cfg(unix)]
+mod fallback {
+    use std::fs::File;
+    use std::os::fd::AsRawFd;
+
+    pub fn reserve(file: &File, bytes: u64) -> std::io::Result<()> {
+        let len = file.metadata()?.len();
+        if len >= bytes {
+            return Ok(());
+        }
+
+        if available_space(file)? < bytes - len {
+            return Err(std::io::Error::from_raw_os_error(libc::ENOSPC));
+        }
+
+        file.set_len(bytes)
+    }
+
+    /// Determines the space available to unprivileged users on the file system of the file.
+    fn available_space(file: &File) -> std::io::Result<u64> {
+        let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+
+        // SAFETY: The file descriptor is valid and `stat` is a valid out pointer.
+        if unsafe { libc::fstatvfs(file.as_raw_fd(), stat.as_mut_ptr()) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        // SAFETY: fstatvfs succeeded and initialized the structure.
+        let stat = unsafe { stat.assume_init() };
+
+        // The field widths differ between platforms.
+        #[allow(clippy::unnecessary_cast, clippy::useless_conversion)]
+        Ok(u64::from(stat.f_bavail).saturating_mul(stat.f_frsize as u64))
+    }
+}
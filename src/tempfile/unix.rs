@@ -0,0 +1,193 @@
+use super::{describe_missing_directory, TempFile, FILE_PREFIX};
+use crate::collision::{self, random_name, DEFAULT_ATTEMPTS};
+use crate::{Error, Ownership};
+use std::borrow::Borrow;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::path::Path;
+
+impl TempFile {
+    /// Controls whether the file descriptor of this handle is inherited by child processes.
+    ///
+    /// By default, file descriptors are opened with the `FD_CLOEXEC` flag set, i.e. they are
+    /// closed when a child process is executed. Setting `inheritable` to `true` clears the flag,
+    /// so that a process spawned afterwards can access the file via the same descriptor number
+    /// (see [`AsRawFd::as_raw_fd`] on the inner [`File`](tokio::fs::File)).
+    ///
+    /// This only affects the descriptor of this handle, not the ones of other
+    /// handles to the same file.
+    ///
+    /// ## Security
+    ///
+    /// An inheritable descriptor is passed to _every_ child process spawned while the flag
+    /// is cleared, including processes spawned concurrently by other threads or libraries.
+    /// These processes gain access to the file contents regardless of the file's permissions,
+    /// and the descriptor keeps the file alive for as long as they hold it open.
+    /// Restore the flag as soon as the intended child process was spawned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// file.set_inheritable(true)?;
+    /// // ... spawn the child process ...
+    /// file.set_inheritable(false)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_inheritable(&self, inheritable: bool) -> Result<(), Error> {
+        let fd = self.file.as_raw_fd();
+
+        // SAFETY: The descriptor is valid for the lifetime of this handle.
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        if flags < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        let flags = if inheritable {
+            flags & !libc::FD_CLOEXEC
+        } else {
+            flags | libc::FD_CLOEXEC
+        };
+
+        // SAFETY: The descriptor is valid for the lifetime of this handle.
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, flags) } < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        Ok(())
+    }
+
+    /// Changes the owner and/or group of the file.
+    ///
+    /// This is intended for privileged processes handing files to less privileged consumers.
+    /// Changing the owner requires privileges (e.g. `CAP_CHOWN`); without them, only the group
+    /// can be changed, and only to a group the current user is a member of.
+    ///
+    /// ## Arguments
+    ///
+    /// * `uid` - The new owner, or `None` to keep the current owner.
+    /// * `gid` - The new group, or `None` to keep the current group.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] with [`std::io::ErrorKind::PermissionDenied`] if the
+    /// process lacks the required privileges.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new().await?;
+    /// // Keeping the current owner and group is always permitted.
+    /// file.chown(None, None).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub async fn chown(&self, uid: Option<u32>, gid: Option<u32>) -> Result<(), Error> {
+        let file = self.file.try_clone().await?.into_std().await;
+        tokio::task::spawn_blocking(move || std::os::unix::fs::fchown(&file, uid, gid))
+            .await
+            .map_err(std::io::Error::other)??;
+        Ok(())
+    }
+
+    /// Creates a new temporary file in the specified location, owned by the specified
+    /// user and/or group.
+    ///
+    /// The ownership is changed before the file is returned; if this fails, the file is
+    /// removed again. Note that the file briefly exists with the ownership of the current
+    /// process. See [`TempFile::chown`] for the required privileges.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dir` - The directory to create the file in.
+    /// * `uid` - The owner of the file, or `None` to keep the current user.
+    /// * `gid` - The group of the file, or `None` to keep the current group.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new_with_owner_in(std::env::temp_dir(), None, None).await?;
+    /// assert!(file.file_path().is_file());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub async fn new_with_owner_in<P: Borrow<Path>>(
+        dir: P,
+        uid: Option<u32>,
+        gid: Option<u32>,
+    ) -> Result<Self, Error> {
+        // A failure drops the owned file, removing it again.
+        let file = Self::new_in(dir).await?;
+        file.chown(uid, gid).await?;
+        Ok(file)
+    }
+
+    /// Creates a new temporary file in the specified location with the specified permissions.
+    /// When the instance goes out of scope, the file will be deleted.
+    ///
+    /// The mode is applied when creating the file, so the file never exists with broader
+    /// permissions, e.g. `0o600` to keep its contents private. As usual, the process umask
+    /// is applied to the mode; see [`TempFile::set_permissions`] to change the permissions
+    /// regardless of the umask.
+    ///
+    /// ## Arguments
+    ///
+    /// * `mode` - The permission bits of the file.
+    /// * `dir` - The directory to create the file in.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempFile;
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # let _ = tokio_test::block_on(async {
+    /// let file = TempFile::new_with_mode_in(0o600, std::env::temp_dir()).await?;
+    /// let mode = file.metadata().await?.permissions().mode();
+    /// assert_eq!(mode & 0o777, 0o600);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub async fn new_with_mode_in<P: Borrow<Path>>(mode: u32, dir: P) -> Result<Self, Error> {
+        let dir = dir.borrow();
+
+        // The file must be new for the mode to apply.
+        let mut options = tokio::fs::OpenOptions::new();
+        options.create_new(true).read(true).write(true).mode(mode);
+
+        collision::retry(DEFAULT_ATTEMPTS, || async {
+            let path = dir.join(random_name(FILE_PREFIX));
+            match Self::open_internal(&path, &options, false).await {
+                Ok((file, permit)) => Self::wrap(file, permit, &path, Ownership::Owned, None).await,
+                Err(Error::Io { source, .. }) => {
+                    Err(describe_missing_directory(source, &path, dir).await)
+                }
+                Err(e) => Err(e),
+            }
+        })
+        .await
+    }
+}
+
+/// Forwarding AsRawFd to the embedded File
+impl AsRawFd for TempFile {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
+
+/// Forwarding AsFd to the embedded File
+impl AsFd for TempFile {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.file.as_fd()
+    }
+}
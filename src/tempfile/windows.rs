@@ -0,0 +1,16 @@
+use super::TempFile;
+use std::os::windows::io::{AsHandle, AsRawHandle, BorrowedHandle, RawHandle};
+
+/// Forwarding AsRawHandle to the embedded File
+impl AsRawHandle for TempFile {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.file.as_raw_handle()
+    }
+}
+
+/// Forwarding AsHandle to the embedded File
+impl AsHandle for TempFile {
+    fn as_handle(&self) -> BorrowedHandle<'_> {
+        self.file.as_handle()
+    }
+}
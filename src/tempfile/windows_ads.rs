@@ -0,0 +1,98 @@
+use super::TempFile;
+use std::ffi::OsString;
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+use windows_sys::Win32::Storage::FileSystem::{
+    DeleteFileW, FindClose, FindFirstStreamW, FindNextStreamW, FindStreamInfoStandard,
+    WIN32_FIND_STREAM_DATA,
+};
+
+/// The name of the unnamed default data stream, which holds the actual file contents.
+const DEFAULT_STREAM: &str = "::$DATA";
+
+impl TempFile {
+    /// Removes all alternate data streams attached to the file when the last
+    /// reference to it is dropped.
+    ///
+    /// Deleting an owned file removes its alternate data streams as well; this is mostly
+    /// useful for borrowed files (see [`TempFile::from_existing`]) that should be kept,
+    /// but not carry any streams created while they were in use.
+    ///
+    /// The cleanup is best-effort: streams that cannot be enumerated or removed are
+    /// silently left behind.
+    ///
+    /// ## Platform support
+    ///
+    /// This method is available on Windows only and requires the `windows-ads` feature.
+    #[cfg_attr(docsrs, doc(cfg(all(windows, feature = "windows-ads"))))]
+    pub fn with_alternate_stream_cleanup(self) -> Self {
+        self.core
+            .remove_alternate_streams
+            .store(true, Ordering::SeqCst);
+        self
+    }
+}
+
+/// Removes all alternate data streams of the file at the specified path, ignoring any errors.
+pub(super) fn remove_alternate_streams(path: &Path) {
+    for stream in alternate_streams(path) {
+        let mut stream_path = path.as_os_str().to_owned();
+        stream_path.push(&stream);
+        let wide = to_wide(stream_path.as_ref());
+
+        // SAFETY: The path is a valid, null-terminated wide string.
+        unsafe { DeleteFileW(wide.as_ptr()) };
+    }
+}
+
+/// Enumerates the names of all alternate data streams of the file at the specified path,
+/// in the form `:name:$DATA`.
+fn alternate_streams(path: &Path) -> Vec<OsString> {
+    let wide = to_wide(path);
+    let mut data = std::mem::MaybeUninit::<WIN32_FIND_STREAM_DATA>::uninit();
+
+    // SAFETY: The path is a valid, null-terminated wide string and `data` is a valid out pointer.
+    let handle = unsafe {
+        FindFirstStreamW(
+            wide.as_ptr(),
+            FindStreamInfoStandard,
+            data.as_mut_ptr().cast(),
+            0,
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        return Vec::new();
+    }
+
+    let mut streams = Vec::new();
+    loop {
+        // SAFETY: FindFirstStreamW or FindNextStreamW succeeded and initialized the structure.
+        let name = stream_name(unsafe { data.assume_init_ref() });
+        if name != DEFAULT_STREAM {
+            streams.push(name);
+        }
+
+        // SAFETY: The handle is valid and `data` is a valid out pointer.
+        if unsafe { FindNextStreamW(handle, data.as_mut_ptr().cast()) } == 0 {
+            break;
+        }
+    }
+
+    // SAFETY: The handle was obtained from FindFirstStreamW and is closed exactly once.
+    unsafe { FindClose(handle) };
+    streams
+}
+
+/// Extracts the null-terminated stream name.
+fn stream_name(data: &WIN32_FIND_STREAM_DATA) -> OsString {
+    let name = &data.cStreamName;
+    let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+    OsString::from_wide(&name[..len])
+}
+
+/// Converts the path into a null-terminated wide string.
+fn to_wide(path: &Path) -> Vec<u16> {
+    path.as_os_str().encode_wide().chain(Some(0)).collect()
+}
@@ -0,0 +1,88 @@
+use std::borrow::Borrow;
+use std::fmt::{Debug, Formatter};
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// A guard for a temporary path that will be cleaned up automatically when dropped.
+///
+/// Unlike [`TempFile`](crate::TempFile), a `TempPath` does not hold an open handle;
+/// whatever exists at the path when the guard is dropped is removed, be it a file
+/// or a directory. This makes it suitable for paths that are populated by other
+/// processes. See [`TempPath::new_in`] and
+/// [`TempFile::reserve_name_in`](crate::TempFile::reserve_name_in).
+pub struct TempPath {
+    path: PathBuf,
+}
+
+impl TempPath {
+    pub(crate) fn guard(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Returns the guarded path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keeps whatever exists at the path and returns the path, disarming the guard.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use async_tempfile::TempPath;
+    /// # let _ = tokio_test::block_on(async {
+    /// let path = TempPath::new().await?.persist();
+    /// assert!(path.is_file());
+    /// # tokio::fs::remove_file(path).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn persist(self) -> PathBuf {
+        // The Drop implementation must not run; the remaining empty path does not allocate.
+        let mut this = ManuallyDrop::new(self);
+        std::mem::take(&mut this.path)
+    }
+}
+
+/// Removes whatever exists at the path, ignoring any errors.
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let Ok(metadata) = std::fs::symlink_metadata(&self.path) else {
+            return;
+        };
+
+        let _ = if metadata.is_dir() {
+            std::fs::remove_dir_all(&self.path)
+        } else {
+            std::fs::remove_file(&self.path)
+        };
+    }
+}
+
+impl Debug for TempPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.path)
+    }
+}
+
+/// Allows implicit treatment of TempPath as a Path.
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.path
+    }
+}
+
+impl Borrow<Path> for TempPath {
+    fn borrow(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,54 @@
+use crate::Ownership;
+use std::path::Path;
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+use std::path::PathBuf;
+
+/// Records the creation of a temporary file or directory, where `kind` names which.
+/// Like the other functions of this module, this does nothing without the `tracing` feature.
+#[inline(always)]
+pub(crate) fn created(kind: &'static str, path: &Path, ownership: Ownership) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(kind, path = %path.display(), ?ownership, "created temporary {kind}");
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (kind, path, ownership);
+}
+
+/// Records the deletion of a temporary file when the last reference was dropped,
+/// warning if it could not be deleted.
+#[inline(always)]
+pub(crate) fn file_deleted(path: &Path, result: &std::io::Result<()>) {
+    #[cfg(feature = "tracing")]
+    match result {
+        Ok(()) => tracing::debug!(path = %path.display(), "deleted temporary file"),
+        Err(error) => {
+            tracing::warn!(path = %path.display(), %error, "failed to delete temporary file")
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (path, result);
+}
+
+/// Records the deletion of a temporary directory when the last reference was dropped,
+/// warning about each entry that could not be removed.
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+#[inline(always)]
+pub(crate) fn dir_deleted(path: &Path, failed: &[(PathBuf, std::io::Error)]) {
+    #[cfg(feature = "tracing")]
+    if failed.is_empty() {
+        tracing::debug!(path = %path.display(), "deleted temporary directory");
+    } else {
+        for (entry, error) in failed {
+            tracing::warn!(
+                path = %path.display(),
+                entry = %entry.display(),
+                %error,
+                "failed to delete temporary directory entry"
+            );
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (path, failed);
+}
@@ -0,0 +1,32 @@
+//! Tests for the cleanup error callback. These live in their own test binary,
+//! since the callback can only be registered once per process.
+
+use async_tempfile::{TempDir, TempFile};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+static FAILED: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+#[tokio::test]
+async fn reports_files_that_cannot_be_deleted() {
+    assert!(async_tempfile::on_cleanup_error(|path, _| {
+        FAILED.lock().unwrap().push(path.to_path_buf());
+    }));
+    assert!(!async_tempfile::on_cleanup_error(|_, _| {}));
+
+    // A directory in place of the file cannot be removed as a file.
+    let dir = TempDir::new().await.unwrap();
+    let file = TempFile::new_in(dir.as_path()).await.unwrap();
+    let path = file.file_path().clone();
+    tokio::fs::remove_file(&path).await.unwrap();
+    tokio::fs::create_dir(&path).await.unwrap();
+    drop(file);
+    assert_eq!(*FAILED.lock().unwrap(), vec![path.clone()]);
+
+    // Files deleted by someone else are not reported.
+    tokio::fs::remove_dir(&path).await.unwrap();
+    let file = TempFile::new_in(dir.as_path()).await.unwrap();
+    tokio::fs::remove_file(file.file_path()).await.unwrap();
+    drop(file);
+    assert_eq!(FAILED.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,70 @@
+//! Tests for limiting concurrently open files. These live in their own test binary,
+//! since the limit is global to the process.
+
+use async_tempfile::TempFile;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Serializes the tests, as they configure different limits.
+static LIMIT: Mutex<()> = Mutex::const_new(());
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn limited_opens_complete() {
+    let _guard = LIMIT.lock().await;
+    TempFile::set_max_concurrent_opens(Some(2));
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let tasks = (0..32)
+        .map(|_| {
+            let dir = dir.dir_path().clone();
+            tokio::spawn(async move {
+                let file = TempFile::new_in(dir).await.unwrap();
+                file.open_ro().await.unwrap();
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    // Permits are released on failure as well.
+    for _ in 0..4 {
+        assert!(TempFile::new_in(dir.join("missing")).await.is_err());
+    }
+    TempFile::new_in(&dir).await.unwrap();
+
+    TempFile::set_max_concurrent_opens(None);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn opens_wait_while_files_are_alive() {
+    let _guard = LIMIT.lock().await;
+    TempFile::set_max_concurrent_opens(Some(2));
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let first = TempFile::new_in(&dir).await.unwrap();
+    let second = TempFile::new_in(&dir).await.unwrap();
+
+    // Additional handles to open files do not count against the limit.
+    let handle = first.open_ro().await.unwrap();
+    drop(handle);
+
+    // The third file waits for as long as both files are alive, including all their handles.
+    let clone = second.try_clone().await.unwrap();
+    let pending = tokio::time::timeout(Duration::from_millis(200), TempFile::new_in(&dir)).await;
+    assert!(pending.is_err());
+
+    drop(second);
+    let pending = tokio::time::timeout(Duration::from_millis(200), TempFile::new_in(&dir)).await;
+    assert!(pending.is_err());
+
+    drop(clone);
+    let third = tokio::time::timeout(Duration::from_secs(10), TempFile::new_in(&dir))
+        .await
+        .expect("the permit of the dropped file was released")
+        .unwrap();
+
+    drop((first, third));
+    TempFile::set_max_concurrent_opens(None);
+}
@@ -0,0 +1,46 @@
+//! Tests for the per-process subdirectory. These live in their own test binary,
+//! since enabling the subdirectory affects all files created in the default location.
+
+use async_tempfile::TempFile;
+
+#[tokio::test]
+async fn files_share_process_subdir() {
+    TempFile::use_process_subdir(true);
+
+    let first = TempFile::new().await.unwrap();
+    let second = TempFile::new_with_name("process_subdir.file")
+        .await
+        .unwrap();
+    let dir = first.file_path().parent().unwrap().to_path_buf();
+    assert_eq!(second.file_path().parent(), Some(dir.as_path()));
+    assert_eq!(
+        dir.file_name().unwrap().to_str().unwrap(),
+        format!("atmpd_{}", std::process::id())
+    );
+
+    // Explicitly placed files are not affected.
+    let explicit = TempFile::new_in(std::env::temp_dir()).await.unwrap();
+    assert_eq!(
+        explicit.file_path().parent(),
+        Some(std::env::temp_dir().as_path())
+    );
+
+    // The directory is kept while any file in it is alive.
+    drop(first);
+    assert!(dir.is_dir());
+    drop(second);
+    assert!(!dir.exists());
+
+    // The directory is recreated on demand.
+    let third = TempFile::new().await.unwrap();
+    assert_eq!(third.file_path().parent(), Some(dir.as_path()));
+    drop(third);
+    assert!(!dir.exists());
+
+    TempFile::use_process_subdir(false);
+    let outside = TempFile::new().await.unwrap();
+    assert_eq!(
+        outside.file_path().parent(),
+        Some(async_tempfile::default_dir().as_path())
+    );
+}
@@ -94,3 +94,2114 @@ async fn uuid_owned_file_is_dropped() {
     assert!(!path.is_file());
     assert!(tokio::fs::remove_file(path).await.is_err());
 }
+
+#[tokio::test]
+async fn parent_dir_is_borrowed() {
+    let file = TempFile::new().await.unwrap();
+    let parent = file.parent_dir().unwrap();
+    assert_eq!(parent.ownership(), async_tempfile::Ownership::Borrowed);
+    assert_eq!(Some(parent.dir_path().as_path()), file.file_path().parent());
+
+    // Dropping the borrowed directory must not delete it or the file.
+    let dir_path = parent.dir_path().clone();
+    drop(parent);
+    assert!(dir_path.is_dir());
+    assert!(file.file_path().is_file());
+}
+
+#[tokio::test]
+async fn counting_file_tracks_written_bytes() {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let mut file = TempFile::new().await.unwrap().counting();
+    assert_eq!(file.bytes_written(), 0);
+
+    file.write_all(b"hello").await.unwrap();
+    let written = file
+        .write_vectored(&[std::io::IoSlice::new(b", world")])
+        .await
+        .unwrap();
+    assert_eq!(written, 7);
+    file.flush().await.unwrap();
+    assert_eq!(file.bytes_written(), 12);
+
+    // Seeking and reading do not affect the count.
+    file.rewind().await.unwrap();
+    let mut content = String::new();
+    file.read_to_string(&mut content).await.unwrap();
+    assert_eq!(content, "hello, world");
+    assert_eq!(file.bytes_written(), 12);
+}
+
+#[tokio::test]
+async fn vectored_positional_io() {
+    use std::io::{IoSlice, IoSliceMut};
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let mut file = TempFile::new().await.unwrap();
+    let written = file
+        .write_vectored_at(&[IoSlice::new(b"abc"), IoSlice::new(b"defg")], 3)
+        .await
+        .unwrap();
+    assert_eq!(written, 7);
+
+    // The gap before the offset is zero-filled.
+    let mut content = Vec::new();
+    file.rewind().await.unwrap();
+    file.read_to_end(&mut content).await.unwrap();
+    assert_eq!(content, b"\0\0\0abcdefg");
+
+    let (mut first, mut second) = ([0u8; 2], [0u8; 8]);
+    let read = file
+        .read_vectored_at(
+            &mut [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)],
+            4,
+        )
+        .await
+        .unwrap();
+
+    // Reading stops at the end of the file.
+    assert_eq!(read, 6);
+    assert_eq!(&first, b"bc");
+    assert_eq!(&second[..4], b"defg");
+
+    // The cursor is left untouched.
+    #[cfg(unix)]
+    assert_eq!(file.stream_position().await.unwrap(), 10);
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn dirs_on_same_filesystem_share_id() {
+    let parent = async_tempfile::TempDir::new().await.unwrap();
+    let nested = async_tempfile::TempDir::new_in(&parent).await.unwrap();
+    assert_eq!(
+        parent.filesystem_id().await.unwrap(),
+        nested.filesystem_id().await.unwrap()
+    );
+}
+
+#[tokio::test]
+#[cfg(target_os = "linux")]
+async fn filesystem_type_name_does_not_fail() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    dir.filesystem_type_name().await.unwrap();
+}
+
+#[tokio::test]
+async fn creating_file_in_removed_dir_fails() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let dir_path = dir.dir_path().clone();
+
+    // Removes the directory between obtaining the path and creating the file.
+    drop(dir);
+
+    let result = TempFile::new_in(dir_path.clone()).await;
+    assert!(matches!(
+        result,
+        Err(async_tempfile::Error::DirectoryNotFound(path)) if path == dir_path
+    ));
+}
+
+#[tokio::test]
+async fn creating_file_in_file_fails() {
+    let file = TempFile::new().await.unwrap();
+    let result = TempFile::new_in(file.file_path().as_path()).await;
+    assert!(matches!(
+        result,
+        Err(async_tempfile::Error::NotADirectory(path)) if &path == file.file_path()
+    ));
+}
+
+#[tokio::test]
+async fn creating_dir_in_file_or_missing_dir_fails() {
+    let file = TempFile::new().await.unwrap();
+    let result = async_tempfile::TempDir::new_in(file.file_path().as_path()).await;
+    assert!(matches!(
+        result,
+        Err(async_tempfile::Error::NotADirectory(path)) if &path == file.file_path()
+    ));
+
+    let missing = file.file_path().with_extension("missing");
+    let result = async_tempfile::TempDir::new_in(missing.as_path()).await;
+    assert!(matches!(
+        result,
+        Err(async_tempfile::Error::DirectoryNotFound(path)) if path == missing
+    ));
+}
+
+#[tokio::test]
+async fn custom_deleter_is_called_once_on_last_drop() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let file = TempFile::new().await.unwrap().with_deleter({
+        let calls = calls.clone();
+        move |_path| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    let path = file.file_path().clone();
+    let clone = file.open_ro().await.unwrap();
+
+    drop(file);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    drop(clone);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // The deleter replaced the removal, so the file is still there.
+    assert!(path.is_file());
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+#[cfg(target_os = "linux")]
+async fn inheritable_file_is_readable_by_child() {
+    use std::os::fd::AsRawFd;
+    use std::process::Command;
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = TempFile::new().await.unwrap();
+    file.write_all(b"inherited").await.unwrap();
+    file.flush().await.unwrap();
+
+    let fd = file.as_raw_fd();
+    let read_from_child = || {
+        Command::new("cat")
+            .arg(format!("/dev/fd/{fd}"))
+            .output()
+            .unwrap()
+    };
+
+    // Descriptors are not inherited by default.
+    assert!(!read_from_child().status.success());
+
+    file.set_inheritable(true).unwrap();
+    let output = read_from_child();
+    file.set_inheritable(false).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"inherited");
+}
+
+#[tokio::test]
+async fn ownership_change_affects_all_references() {
+    let file = TempFile::new().await.unwrap();
+    let clone = file.open_ro().await.unwrap();
+    let path = file.file_path().clone();
+
+    let file = file.into_borrowed();
+    assert_eq!(clone.ownership(), async_tempfile::Ownership::Borrowed);
+
+    drop(file);
+    drop(clone);
+    assert!(path.is_file());
+
+    // Taking ownership again deletes the file.
+    let file = TempFile::from_existing(path.clone(), async_tempfile::Ownership::Borrowed)
+        .await
+        .unwrap()
+        .into_owned();
+    drop(file);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn borrowed_file_can_be_refilled() {
+    use tokio::io::AsyncReadExt;
+
+    let path = TempFile::new()
+        .await
+        .unwrap()
+        .into_borrowed()
+        .file_path()
+        .clone();
+
+    let mut file = TempFile::from_existing(path.clone(), async_tempfile::Ownership::Borrowed)
+        .await
+        .unwrap();
+
+    let written = file
+        .fill_from(&b"a longer first content"[..])
+        .await
+        .unwrap();
+    assert_eq!(written, 22);
+
+    // Refilling with shorter content must not leave stale data behind.
+    let written = file.fill_from(&b"second"[..]).await.unwrap();
+    assert_eq!(written, 6);
+
+    let mut content = String::new();
+    file.read_to_string(&mut content).await.unwrap();
+    assert_eq!(content, "second");
+
+    drop(file);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), b"second");
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+#[cfg(all(windows, feature = "windows-ads"))]
+async fn alternate_streams_are_removed_from_borrowed_file() {
+    let path = TempFile::new()
+        .await
+        .unwrap()
+        .into_borrowed()
+        .file_path()
+        .clone();
+
+    let mut stream_path = path.clone().into_os_string();
+    stream_path.push(":extra");
+    tokio::fs::write(&stream_path, b"stream").await.unwrap();
+
+    let file = TempFile::from_existing(path.clone(), async_tempfile::Ownership::Borrowed)
+        .await
+        .unwrap()
+        .with_alternate_stream_cleanup();
+    drop(file);
+
+    assert!(path.is_file());
+    assert!(tokio::fs::metadata(&stream_path).await.is_err());
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+async fn file_can_be_created_beside_handle() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let existing = tokio::fs::File::create(dir.join("existing")).await.unwrap();
+
+    // The resolved directory may differ from the original path if it contains symlinks.
+    let file = TempFile::new_beside(&existing).await.unwrap();
+    let parent = file.file_path().parent().unwrap();
+    assert_eq!(
+        tokio::fs::canonicalize(parent).await.unwrap(),
+        tokio::fs::canonicalize(&dir).await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn fixture_files_are_written_into_dir() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let first = dir.write_file("first.txt", b"first").await.unwrap();
+    let nested = dir.write_file("a/b/nested.txt", "nested").await.unwrap();
+
+    assert!(nested.starts_with(dir.dir_path()));
+    assert_eq!(tokio::fs::read(&first).await.unwrap(), b"first");
+    assert_eq!(tokio::fs::read(&nested).await.unwrap(), b"nested");
+
+    let escaping = dir.write_file("../escaping.txt", b"escaping").await;
+    assert!(matches!(escaping, Err(async_tempfile::Error::InvalidFile)));
+
+    // Failures report the path that could not be created.
+    let error = dir
+        .write_file("first.txt/inner.txt", b"inner")
+        .await
+        .unwrap_err();
+    assert_eq!(error.path(), Some(first.as_path()));
+
+    // The fixtures are removed along with the directory.
+    drop(dir);
+    assert!(!first.exists());
+    assert!(!nested.exists());
+}
+
+#[tokio::test]
+#[cfg(feature = "cap-std")]
+async fn cap_dir_file_is_managed_through_capability() {
+    use cap_std::{ambient_authority, fs::Dir};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let temp = async_tempfile::TempDir::new().await.unwrap();
+    let dir = Dir::open_ambient_dir(temp.dir_path(), ambient_authority()).unwrap();
+
+    let mut file = TempFile::new_with_name_in_cap_dir("capability.file", &dir)
+        .await
+        .unwrap();
+    assert_eq!(file.file_path(), std::path::Path::new("capability.file"));
+    file.write_all(b"sandboxed").await.unwrap();
+    file.flush().await.unwrap();
+
+    // Additional handles are opened relative to the capability, not the working directory.
+    let mut clone = file.open_ro().await.unwrap();
+    let mut content = String::new();
+    clone.read_to_string(&mut content).await.unwrap();
+    assert_eq!(content, "sandboxed");
+
+    // Creating the same file again fails.
+    assert!(TempFile::new_with_name_in_cap_dir("capability.file", &dir)
+        .await
+        .is_err());
+
+    drop(file);
+    assert!(dir.exists("capability.file"));
+    drop(clone);
+    assert!(!dir.exists("capability.file"));
+}
+
+#[tokio::test]
+async fn file_outlives_original_while_clones_remain() {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let file = TempFile::new().await.unwrap();
+    let path = file.file_path().clone();
+    let mut rw = file.open_rw().await.unwrap();
+    let ro = file.open_ro().await.unwrap();
+    let mut cloned = file.try_clone().await.unwrap();
+
+    // Dropping the original closes its handle, but keeps the file and the siblings usable.
+    drop(file);
+    assert!(path.is_file());
+    rw.write_all(b"still alive").await.unwrap();
+    rw.flush().await.unwrap();
+
+    drop(ro);
+    assert!(path.is_file());
+
+    cloned.rewind().await.unwrap();
+    let mut content = String::new();
+    cloned.read_to_string(&mut content).await.unwrap();
+    assert_eq!(content, "still alive");
+
+    drop(cloned);
+    assert!(path.is_file());
+
+    // The last reference deletes the file.
+    drop(rw);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn file_is_deleted_after_last_clone_in_any_order() {
+    for order in [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ] {
+        let file = TempFile::new().await.unwrap();
+        let path = file.file_path().clone();
+        let rw = file.open_rw().await.unwrap();
+        let ro = file.open_ro().await.unwrap();
+
+        let mut handles = [Some(file), Some(rw), Some(ro)];
+        for (dropped, index) in order.into_iter().enumerate() {
+            assert!(path.is_file(), "file deleted early in order {order:?}");
+            drop(handles[index].take());
+            assert_eq!(path.exists(), dropped < 2, "wrong state in order {order:?}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn extension_change_is_visible_to_all_references() {
+    use tokio::io::AsyncWriteExt;
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let mut file = TempFile::new_with_name_in("content.tmp", &dir)
+        .await
+        .unwrap();
+    let clone = file.open_ro().await.unwrap();
+    let old_path = file.file_path().clone();
+
+    file.set_extension("json").await.unwrap();
+    assert_eq!(file.file_path(), &dir.join("content.json"));
+    assert_eq!(clone.file_path(), file.file_path());
+    assert!(!old_path.exists());
+
+    // Open handles keep working after the rename.
+    file.write_all(b"{}").await.unwrap();
+    file.flush().await.unwrap();
+    assert_eq!(tokio::fs::read(file.file_path()).await.unwrap(), b"{}");
+
+    let invalid = file.set_extension("nested/json").await;
+    assert!(matches!(invalid, Err(async_tempfile::Error::InvalidFile)));
+
+    // The renamed file is deleted when the last reference is dropped.
+    let path = file.file_path().clone();
+    drop(file);
+    drop(clone);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn reserved_name_is_cleaned_up() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+
+    let (guard, path) = TempFile::reserve_name_in(&dir).await.unwrap();
+    assert_eq!(guard.path(), path.as_path());
+    assert_eq!(path.parent(), Some(dir.as_path()));
+    assert!(!path.exists());
+
+    // Another process may create the file exclusively.
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .unwrap();
+
+    drop(guard);
+    assert!(!path.exists());
+
+    // The guard also removes directories.
+    let (guard, path) = TempFile::reserve_name_in(&dir).await.unwrap();
+    tokio::fs::create_dir_all(path.join("nested"))
+        .await
+        .unwrap();
+    drop(guard);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn borrowed_file_keeps_written_data() {
+    use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+    let path = TempFile::new()
+        .await
+        .unwrap()
+        .into_borrowed()
+        .file_path()
+        .clone();
+
+    let mut file = TempFile::from_existing(path.clone(), async_tempfile::Ownership::Borrowed)
+        .await
+        .unwrap();
+    let mut clone = file.open_rw().await.unwrap();
+
+    file.write_all(b"written ").await.unwrap();
+    file.flush().await.unwrap();
+    drop(file);
+
+    clone.seek(std::io::SeekFrom::End(0)).await.unwrap();
+    clone.write_all(b"twice").await.unwrap();
+    clone.flush().await.unwrap();
+    drop(clone);
+
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), b"written twice");
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+async fn closing_dir_removes_contents() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let path = dir.dir_path().clone();
+    dir.write_file("a/b/c.txt", b"content").await.unwrap();
+
+    // Closing a reference while others remain keeps the directory.
+    let clone = dir.try_clone().await.unwrap();
+    clone.close().await.unwrap();
+    assert!(path.join("a/b/c.txt").is_file());
+
+    dir.close().await.unwrap();
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn persisting_dir_keeps_it_for_all_references() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    dir.write_file("artifact.bin", b"result").await.unwrap();
+    let clone = dir.try_clone().await.unwrap();
+
+    let path = clone.persist().unwrap();
+    assert_eq!(dir.ownership(), async_tempfile::Ownership::Borrowed);
+    drop(dir);
+    assert!(path.join("artifact.bin").is_file());
+
+    tokio::fs::remove_dir_all(path).await.unwrap();
+}
+
+#[tokio::test]
+async fn create_file_and_dir_in_dir() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let nested = dir.create_dir("nested").await.unwrap();
+    let file = nested.create_file("file.txt").await.unwrap();
+    assert!(dir.join("nested/file.txt").is_file());
+
+    for name in ["", "..", "a/b"] {
+        assert!(matches!(
+            dir.create_file(name).await,
+            Err(async_tempfile::Error::InvalidFile)
+        ));
+        assert!(matches!(
+            dir.create_dir(name).await,
+            Err(async_tempfile::Error::InvalidFile)
+        ));
+    }
+
+    // Nested items are removed before the directory that contains them.
+    let path = dir.dir_path().clone();
+    drop(file);
+    assert!(!path.join("nested/file.txt").exists());
+    drop(nested);
+    assert!(!path.join("nested").exists());
+    drop(dir);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn entries_lists_direct_children() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    assert!(dir.entries().await.unwrap().is_empty());
+
+    dir.write_file("z.txt", "z").await.unwrap();
+    dir.write_file("nested/a.txt", "a").await.unwrap();
+    assert_eq!(
+        dir.entries().await.unwrap(),
+        [dir.join("nested"), dir.join("z.txt")]
+    );
+
+    let mut read_dir = dir.read_dir().await.unwrap();
+    let mut count = 0;
+    while read_dir.next_entry().await.unwrap().is_some() {
+        count += 1;
+    }
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn size_on_disk_sums_files() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    assert_eq!(dir.size_on_disk(usize::MAX).await.unwrap(), 0);
+
+    dir.write_file("a.bin", [0u8; 100]).await.unwrap();
+    dir.write_file("x/y/z/b.bin", [0u8; 23]).await.unwrap();
+    assert_eq!(dir.size_on_disk(usize::MAX).await.unwrap(), 123);
+
+    // Files deeper than the maximum depth are not counted.
+    assert_eq!(dir.size_on_disk(4).await.unwrap(), 123);
+    assert_eq!(dir.size_on_disk(3).await.unwrap(), 100);
+    assert_eq!(dir.size_on_disk(0).await.unwrap(), 0);
+
+    // Symbolic links are not followed, so a loop does not count the files twice.
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(dir.dir_path(), dir.join("x/loop")).unwrap();
+        assert_eq!(dir.size_on_disk(usize::MAX).await.unwrap(), 123);
+    }
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn secure_dir_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = async_tempfile::TempDir::new().await.unwrap();
+    let dir = async_tempfile::TempDir::new_secure_in(root.dir_path().as_path())
+        .await
+        .unwrap();
+    let mode = tokio::fs::metadata(dir.dir_path())
+        .await
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o700);
+
+    let path = dir.dir_path().clone();
+    drop(dir);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn closing_borrowed_dir_keeps_it() {
+    let owner = async_tempfile::TempDir::new().await.unwrap();
+    let path = owner.dir_path().clone();
+
+    let borrowed =
+        async_tempfile::TempDir::from_existing(path.clone(), async_tempfile::Ownership::Borrowed)
+            .await
+            .unwrap();
+    borrowed.close().await.unwrap();
+    assert!(path.is_dir());
+}
+
+#[tokio::test]
+async fn creation_info_reflects_creation() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let file = TempFile::new_with_name_in("created.tmp", &dir)
+        .await
+        .unwrap();
+    file.set_extension("txt").await.unwrap();
+
+    // Renaming does not change how the file was created.
+    let info = file.creation_info();
+    assert_eq!(info.directory(), dir.as_path());
+    assert_eq!(info.name(), "created.tmp");
+    assert_eq!(info.ownership(), async_tempfile::Ownership::Owned);
+    assert_eq!(info.mode(), None);
+
+    let borrowed = TempFile::from_existing(
+        file.file_path().clone(),
+        async_tempfile::Ownership::Borrowed,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        borrowed.creation_info().ownership(),
+        async_tempfile::Ownership::Borrowed
+    );
+    assert_eq!(borrowed.creation_info().name(), "created.txt");
+}
+
+#[tokio::test]
+async fn atomic_save_replaces_target() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let target = dir.join("target.txt");
+
+    TempFile::save_atomic(target.as_path(), b"first")
+        .await
+        .unwrap();
+    assert_eq!(tokio::fs::read(&target).await.unwrap(), b"first");
+
+    TempFile::save_atomic(target.as_path(), b"second")
+        .await
+        .unwrap();
+    assert_eq!(tokio::fs::read(&target).await.unwrap(), b"second");
+
+    // Failing to rename over a directory leaves no temporary files behind.
+    let blocked = dir.join("blocked");
+    tokio::fs::create_dir(&blocked).await.unwrap();
+    tokio::fs::write(blocked.join("child"), b"child")
+        .await
+        .unwrap();
+    let error = TempFile::save_atomic(blocked.as_path(), b"data")
+        .await
+        .unwrap_err();
+
+    // The error refers to the target and keeps the OS error.
+    assert_eq!(error.path(), Some(blocked.as_path()));
+    let source = std::error::Error::source(&error)
+        .and_then(|e| e.downcast_ref::<std::io::Error>())
+        .unwrap();
+    assert!(source.raw_os_error().is_some());
+
+    let mut entries = std::fs::read_dir(dir.dir_path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, ["blocked", "target.txt"]);
+}
+
+#[tokio::test]
+async fn reserve_space_grows_file() {
+    let file = TempFile::new().await.unwrap();
+    file.try_reserve_space(8192).await.unwrap();
+    assert_eq!(file.metadata().await.unwrap().len(), 8192);
+
+    // Smaller reservations never shrink the file.
+    file.try_reserve_space(1024).await.unwrap();
+    assert_eq!(file.metadata().await.unwrap().len(), 8192);
+
+    // Failed reservations leave the file unchanged.
+    assert!(file.try_reserve_space(1 << 62).await.is_err());
+    assert_eq!(file.metadata().await.unwrap().len(), 8192);
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[tokio::test]
+async fn reserve_space_allocates_blocks() {
+    use std::os::unix::fs::MetadataExt;
+
+    let file = TempFile::new().await.unwrap();
+    file.try_reserve_space(1 << 20).await.unwrap();
+
+    // The block count is given in units of 512 bytes.
+    let metadata = file.metadata().await.unwrap();
+    assert!(metadata.blocks() * 512 >= 1 << 20);
+}
+
+#[tokio::test]
+async fn preallocate_grows_file() {
+    let file = TempFile::new().await.unwrap();
+    file.preallocate(1 << 20).await.unwrap();
+    assert_eq!(file.metadata().await.unwrap().len(), 1 << 20);
+
+    // The blocks are allocated where supported.
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    {
+        use std::os::unix::fs::MetadataExt;
+        assert!(file.metadata().await.unwrap().blocks() * 512 >= 1 << 20);
+    }
+
+    // Smaller sizes never shrink the file.
+    file.preallocate(1024).await.unwrap();
+    assert_eq!(file.metadata().await.unwrap().len(), 1 << 20);
+}
+
+// Windows keeps deleted files with open handles around in a delete-pending state.
+#[cfg(unix)]
+#[tokio::test]
+async fn reopening_deleted_file_reports_vanished() {
+    let file = TempFile::new().await.unwrap();
+    tokio::fs::remove_file(file.file_path()).await.unwrap();
+
+    for result in [file.open_ro().await, file.open_rw().await] {
+        match result {
+            Err(async_tempfile::Error::FileVanished(path)) => assert_eq!(&path, file.file_path()),
+            other => panic!("expected a vanished file, got {other:?}"),
+        }
+    }
+
+    // Duplicating the handle does not depend on the path.
+    assert!(file.try_clone().await.is_ok());
+}
+
+#[tokio::test]
+async fn import_file_copies_into_dir() {
+    let source_dir = async_tempfile::TempDir::new().await.unwrap();
+    let source = source_dir
+        .write_file("source.txt", b"imported")
+        .await
+        .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&source, std::fs::Permissions::from_mode(0o640)).unwrap();
+    }
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let file = dir.import_file(source.as_path()).await.unwrap();
+    assert_eq!(file.file_path().parent(), Some(dir.as_path()));
+    assert_eq!(
+        tokio::fs::read(file.file_path()).await.unwrap(),
+        b"imported"
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = file.metadata().await.unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    // The copy keeps the directory alive.
+    let dir_path = dir.dir_path().clone();
+    drop(dir);
+    assert!(file.file_path().is_file());
+
+    let path = file.file_path().clone();
+    drop(file);
+    assert!(!path.exists());
+    assert!(!dir_path.exists());
+    assert!(source.exists());
+
+    let missing = source_dir.join("missing.txt");
+    let error = source_dir.import_file(missing.as_path()).await.unwrap_err();
+    assert_eq!(error.path(), Some(missing.as_path()));
+}
+
+#[tokio::test]
+async fn remove_deletes_file_for_all_references() {
+    let file = TempFile::new().await.unwrap();
+    let clone = file.try_clone().await.unwrap();
+    let path = file.file_path().clone();
+
+    file.remove().await.unwrap();
+    assert!(!path.exists());
+
+    // Operations on remaining references report the removal in debug builds.
+    #[cfg(debug_assertions)]
+    assert!(matches!(
+        clone.try_clone().await,
+        Err(async_tempfile::Error::FileVanished(_))
+    ));
+    drop(clone);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn remove_calls_deleter_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let file = TempFile::new().await.unwrap().with_deleter({
+        let calls = calls.clone();
+        move |_path| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    let path = file.file_path().clone();
+    let clone = file.open_ro().await.unwrap();
+
+    file.remove().await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    drop(clone);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // The deleter replaced the removal, so the file is still there.
+    assert!(path.is_file());
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+async fn cloned_cursors() {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let mut file = TempFile::new().await.unwrap();
+    file.write_all(b"0123456789").await.unwrap();
+    file.flush().await.unwrap();
+    file.rewind().await.unwrap();
+
+    // Clones obtained via `try_clone` share the cursor.
+    let mut shared = file.try_clone().await.unwrap();
+    let mut independent = file.open_rw().await.unwrap();
+
+    file.seek(std::io::SeekFrom::Start(4)).await.unwrap();
+    assert_eq!(shared.stream_position().await.unwrap(), 4);
+    assert_eq!(independent.stream_position().await.unwrap(), 0);
+
+    let mut buf = [0u8; 2];
+    shared.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"45");
+    assert_eq!(file.stream_position().await.unwrap(), 6);
+
+    independent.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"01");
+    assert_eq!(file.stream_position().await.unwrap(), 6);
+
+    // Reopened handles start at the beginning and do not move the original cursor.
+    for mut reopened in [file.open_rw().await.unwrap(), file.open_ro().await.unwrap()] {
+        assert_eq!(reopened.stream_position().await.unwrap(), 0);
+        reopened.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"01");
+        assert_eq!(file.stream_position().await.unwrap(), 6);
+    }
+}
+
+#[tokio::test]
+async fn writes_follow_cursor_sharing() {
+    use tokio::io::AsyncWriteExt;
+
+    // Writes through handles sharing the cursor continue where the other one stopped.
+    let mut file = TempFile::new().await.unwrap();
+    let mut shared = file.try_clone().await.unwrap();
+    file.write_all(b"first ").await.unwrap();
+    file.flush().await.unwrap();
+    shared.write_all(b"second").await.unwrap();
+    shared.flush().await.unwrap();
+    assert_eq!(file.read_all().await.unwrap(), b"first second");
+
+    // Writes through reopened handles start at the beginning, overwriting the data.
+    let mut file = TempFile::new().await.unwrap();
+    let mut reopened = file.open_rw().await.unwrap();
+    file.write_all(b"first ").await.unwrap();
+    file.flush().await.unwrap();
+    reopened.write_all(b"second").await.unwrap();
+    reopened.flush().await.unwrap();
+    assert_eq!(file.read_all().await.unwrap(), b"second");
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn chown_changes_owner_when_privileged() {
+    use std::os::unix::fs::MetadataExt;
+
+    const NOBODY: u32 = 65534;
+
+    let file = TempFile::new().await.unwrap();
+    match file.chown(Some(NOBODY), Some(NOBODY)).await {
+        Ok(()) => {}
+        // Not running as root; nothing to verify.
+        Err(e) if e.kind() == async_tempfile::ErrorKind::PermissionDenied => return,
+        Err(e) => panic!("unexpected error: {e}"),
+    }
+
+    let metadata = file.metadata().await.unwrap();
+    assert_eq!((metadata.uid(), metadata.gid()), (NOBODY, NOBODY));
+
+    let owned = TempFile::new_with_owner_in(std::env::temp_dir(), Some(NOBODY), None)
+        .await
+        .unwrap();
+    assert_eq!(owned.metadata().await.unwrap().uid(), NOBODY);
+}
+
+#[tokio::test]
+async fn walk_is_depth_limited() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    dir.write_file("a/b/c/d.txt", "deep").await.unwrap();
+    dir.write_file("e.txt", "flat").await.unwrap();
+
+    assert!(dir.walk(0).await.unwrap().is_empty());
+    assert_eq!(
+        dir.walk(1).await.unwrap(),
+        [dir.join("a"), dir.join("e.txt")]
+    );
+    assert_eq!(dir.walk(10).await.unwrap().len(), 5);
+
+    // Symbolic links are listed but not followed.
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(dir.as_path(), dir.join("a/b/loop")).unwrap();
+        let entries = dir.walk(usize::MAX).await.unwrap();
+        assert_eq!(entries.len(), 6);
+        assert!(entries.contains(&dir.join("a/b/loop")));
+    }
+}
+
+#[tokio::test]
+async fn peek_keeps_position() {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let mut file = TempFile::new().await.unwrap();
+    file.fill_from(&b"0123456789"[..]).await.unwrap();
+    file.seek(std::io::SeekFrom::Start(6)).await.unwrap();
+
+    assert_eq!(file.peek(2).await.unwrap(), b"67");
+    assert_eq!(file.stream_position().await.unwrap(), 6);
+
+    // Peeking past the end returns the remaining data only.
+    assert_eq!(file.peek(100).await.unwrap(), b"6789");
+
+    let mut rest = Vec::new();
+    file.read_to_end(&mut rest).await.unwrap();
+    assert_eq!(rest, b"6789");
+}
+
+#[tokio::test]
+async fn persist_keeps_file_for_all_references() {
+    use tokio::io::AsyncWriteExt;
+
+    let file = TempFile::new().await.unwrap();
+    let mut writer = file.open_rw().await.unwrap();
+
+    let path = file.persist().unwrap();
+    writer.write_all(b"kept").await.unwrap();
+    writer.flush().await.unwrap();
+    drop(writer);
+
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), b"kept");
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+async fn builder_generates_name_from_pattern() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let file = TempFile::builder()
+        .prefix("pre_")
+        .suffix("_post")
+        .extension("json")
+        .dir(dir.as_path())
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(file.file_path().parent(), Some(dir.as_path()));
+    let name = file.file_path().file_name().unwrap().to_str().unwrap();
+    assert!(name.starts_with("pre_"));
+    assert!(name.ends_with("_post.json"));
+
+    // Without an extension, there is no trailing dot.
+    let file = TempFile::builder().suffix("_post").build().await.unwrap();
+    assert!(file.file_path().to_str().unwrap().ends_with("_post"));
+
+    assert!(matches!(
+        TempFile::builder().prefix("nested/").build().await,
+        Err(async_tempfile::Error::InvalidFile)
+    ));
+}
+
+#[tokio::test]
+async fn reference_count_tracks_clones() {
+    let file = TempFile::new().await.unwrap();
+    assert_eq!(file.reference_count(), 1);
+    assert!(file.is_last_reference());
+
+    let rw = file.open_rw().await.unwrap();
+    let cloned = file.try_clone().await.unwrap();
+    assert_eq!(file.reference_count(), 3);
+    assert_eq!(cloned.reference_count(), 3);
+
+    drop(rw);
+    drop(cloned);
+    assert!(file.is_last_reference());
+}
+
+#[tokio::test]
+async fn will_delete_on_drop_accounts_for_clones_and_ownership() {
+    let file = TempFile::new().await.unwrap();
+    assert!(file.will_delete_on_drop());
+
+    let cloned = file.try_clone().await.unwrap();
+    assert!(!file.will_delete_on_drop());
+    assert!(!cloned.will_delete_on_drop());
+    drop(cloned);
+    assert!(file.will_delete_on_drop());
+
+    let path = file.file_path().clone();
+    let borrowed = TempFile::from_existing(path.as_path(), async_tempfile::Ownership::Borrowed)
+        .await
+        .unwrap();
+    assert!(!borrowed.will_delete_on_drop());
+
+    let file = file.into_borrowed();
+    assert!(!file.will_delete_on_drop());
+    drop(file);
+    assert!(path.is_file());
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+async fn len_follows_writes() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = TempFile::new().await.unwrap();
+    assert!(file.is_empty().await.unwrap());
+    assert_eq!(file.len().await.unwrap(), 0);
+
+    file.write_all(b"0123456789").await.unwrap();
+    file.flush().await.unwrap();
+    assert!(!file.is_empty().await.unwrap());
+    assert_eq!(file.len().await.unwrap(), 10);
+    assert!(file.metadata().await.unwrap().is_file());
+}
+
+#[tokio::test]
+async fn child_file_keeps_dir_alive() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let dir_path = dir.dir_path().clone();
+    let first = dir.child_file().await.unwrap();
+    let second = dir.child_file_with_name("child.txt").await.unwrap();
+    assert_eq!(first.file_path().parent(), Some(dir_path.as_path()));
+    assert_eq!(second.file_path(), &dir_path.join("child.txt"));
+
+    drop(dir);
+    assert!(first.file_path().is_file());
+
+    drop(first);
+    assert!(second.file_path().is_file());
+
+    let path = second.file_path().clone();
+    drop(second);
+    assert!(!path.exists());
+    assert!(!dir_path.exists());
+}
+
+#[tokio::test]
+async fn close_deletes_with_last_reference() {
+    let file = TempFile::new().await.unwrap();
+    let path = file.file_path().clone();
+    let clone = file.try_clone().await.unwrap();
+
+    file.close().await.unwrap();
+    assert!(path.is_file());
+
+    clone.close().await.unwrap();
+    assert!(!path.exists());
+
+    // Borrowed files are kept.
+    let file = TempFile::new().await.unwrap().into_borrowed();
+    let path = file.file_path().clone();
+    file.close().await.unwrap();
+    assert!(path.is_file());
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn close_reports_deletion_errors() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let file = TempFile::new_in(dir.as_path()).await.unwrap();
+    let path = file.file_path().clone();
+    std::fs::set_permissions(dir.as_path(), std::fs::Permissions::from_mode(0o500)).unwrap();
+
+    let result = file.close().await;
+    std::fs::set_permissions(dir.as_path(), std::fs::Permissions::from_mode(0o700)).unwrap();
+
+    // Privileged users may delete the file regardless of the permissions.
+    if path.exists() {
+        assert!(result.is_err());
+    }
+}
+
+#[tokio::test]
+async fn generator_names_files_and_dirs() {
+    use async_tempfile::{NameGenerator, TempDir};
+
+    struct Fixed(&'static str);
+
+    impl NameGenerator for Fixed {
+        fn generate(&self, prefix: &str) -> String {
+            format!("{prefix}{}", self.0)
+        }
+    }
+
+    let dir = TempDir::new_with_generator(&Fixed("generated"), std::env::temp_dir())
+        .await
+        .unwrap();
+    assert!(dir.dir_path().ends_with("atmpd_generated"));
+
+    let file = TempFile::new_with_generator(&Fixed("generated"), dir.as_path())
+        .await
+        .unwrap();
+    assert_eq!(file.file_path(), &dir.join("atmp_generated"));
+
+    assert!(matches!(
+        TempFile::new_with_generator(&Fixed("a/b"), dir.as_path()).await,
+        Err(async_tempfile::Error::InvalidFile)
+    ));
+    assert!(matches!(
+        TempDir::new_with_generator(&Fixed("a/b"), dir.as_path()).await,
+        Err(async_tempfile::Error::InvalidFile)
+    ));
+}
+
+#[tokio::test]
+#[cfg(target_os = "linux")]
+async fn anonymous_file_has_no_name() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let mut file = match TempFile::new_anonymous_in(dir.as_path()).await {
+        Ok(file) => file,
+        // The file system does not support O_TMPFILE.
+        Err(async_tempfile::Error::Unsupported) => return,
+        Err(e) => panic!("unexpected error: {e}"),
+    };
+    assert_eq!(file.file_path(), dir.dir_path());
+    assert!(dir.walk(1).await.unwrap().is_empty());
+
+    file.write_all(b"anonymous").await.unwrap();
+    file.flush().await.unwrap();
+
+    let mut reader = file.open_ro().await.unwrap();
+    let mut content = String::new();
+    reader.read_to_string(&mut content).await.unwrap();
+    assert_eq!(content, "anonymous");
+
+    assert!(matches!(
+        file.set_extension("txt").await,
+        Err(async_tempfile::Error::Unsupported)
+    ));
+
+    drop(reader);
+    drop(file);
+    assert!(dir.as_path().is_dir());
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn raw_fd_is_exposed() {
+    use std::os::fd::{AsFd, AsRawFd};
+
+    let file = TempFile::new().await.unwrap();
+    let fd = file.as_raw_fd();
+    assert!(fd >= 0);
+    assert_eq!(file.as_fd().as_raw_fd(), fd);
+}
+
+#[tokio::test]
+#[cfg(windows)]
+async fn raw_handle_is_exposed() {
+    use std::os::windows::io::{AsHandle, AsRawHandle};
+
+    let file = TempFile::new().await.unwrap();
+    let handle = file.as_raw_handle();
+    assert!(!handle.is_null());
+    assert_eq!(file.as_handle().as_raw_handle(), handle);
+}
+
+#[tokio::test]
+async fn write_all_then_read_to_end() {
+    let mut file = TempFile::new().await.unwrap();
+    file.write_all_and_flush(b"first ").await.unwrap();
+    file.write_all_and_flush(b"second").await.unwrap();
+
+    // The data was flushed and is visible to other handles.
+    let mut reader = file.open_ro().await.unwrap();
+    assert_eq!(reader.read_all().await.unwrap(), b"first second");
+
+    // The cursor is rewound before reading.
+    assert_eq!(file.read_all().await.unwrap(), b"first second");
+    assert_eq!(file.read_all().await.unwrap(), b"first second");
+}
+
+#[tokio::test]
+async fn from_bytes_is_rewound() {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let mut file = TempFile::from_bytes(b"fixture").await.unwrap();
+    assert_eq!(file.stream_position().await.unwrap(), 0);
+
+    let mut content = Vec::new();
+    file.read_to_end(&mut content).await.unwrap();
+    assert_eq!(content, b"fixture");
+
+    let dir = std::env::temp_dir();
+    let file = TempFile::from_bytes_in(b"", dir.as_path()).await.unwrap();
+    assert!(file.file_path().starts_with(&dir));
+    assert!(file.is_empty().await.unwrap());
+}
+
+#[tokio::test]
+async fn from_reader_streams_contents() {
+    use tokio::io::AsyncSeekExt;
+
+    // A reader larger than the copy buffer, yielding the data in small chunks.
+    let data: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+    let mut reader = tokio_test::io::Builder::new()
+        .read(&data[..1000])
+        .read(&data[1000..])
+        .build();
+
+    let (mut file, copied) = TempFile::from_reader(&mut reader).await.unwrap();
+    assert_eq!(copied, data.len() as u64);
+    assert_eq!(file.stream_position().await.unwrap(), 0);
+    assert_eq!(file.read_all().await.unwrap(), data);
+}
+
+#[tokio::test]
+async fn persist_to_moves_file() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let dest = dir.join("kept.bin");
+
+    let file = TempFile::from_bytes_in(b"first", dir.dir_path().as_path())
+        .await
+        .unwrap();
+    let other = file.open_ro().await.unwrap();
+    let original = file.file_path().clone();
+    file.persist_to(dest.as_path()).await.unwrap();
+
+    // All references see the new path, and the file is kept.
+    assert_eq!(other.file_path(), &dest);
+    drop(other);
+    assert!(!original.exists());
+    assert_eq!(tokio::fs::read(&dest).await.unwrap(), b"first");
+
+    // Existing files are replaced.
+    let file = TempFile::from_bytes_in(b"second", dir.dir_path().as_path())
+        .await
+        .unwrap();
+    file.persist_to(dest.as_path()).await.unwrap();
+    assert_eq!(tokio::fs::read(&dest).await.unwrap(), b"second");
+}
+
+#[tokio::test]
+async fn persist_to_noclobber_keeps_existing_file() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let dest = dir.join("kept.bin");
+
+    let file = TempFile::from_bytes_in(b"first", dir.dir_path().as_path())
+        .await
+        .unwrap();
+    let original = file.file_path().clone();
+    file.persist_to_noclobber(dest.as_path()).await.unwrap();
+    assert!(!original.exists());
+
+    let file = TempFile::from_bytes_in(b"second", dir.dir_path().as_path())
+        .await
+        .unwrap();
+    let original = file.file_path().clone();
+    let error = file.persist_to_noclobber(dest.as_path()).await.unwrap_err();
+    assert!(
+        matches!(&error, async_tempfile::Error::Io { source, .. } if source.kind() == std::io::ErrorKind::AlreadyExists)
+    );
+
+    // The rejected file was deleted on drop.
+    assert!(!original.exists());
+    assert_eq!(tokio::fs::read(&dest).await.unwrap(), b"first");
+}
+
+#[tokio::test]
+async fn new_near_uses_parent_of_target() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let target = dir.join("missing.txt");
+    let file = TempFile::new_near(target.as_path()).await.unwrap();
+    assert_eq!(file.file_path().parent(), Some(dir.dir_path().as_path()));
+
+    // Bare file names resolve to the current directory.
+    let file = TempFile::new_near(std::path::Path::new("target.txt"))
+        .await
+        .unwrap();
+    assert_eq!(file.file_path().parent(), Some(std::path::Path::new(".")));
+    assert!(file.file_path().is_file());
+}
+
+#[tokio::test]
+async fn from_existing_with_options_controls_mode() {
+    use async_tempfile::{Error, Ownership};
+    use tokio::fs::OpenOptions;
+
+    let existing = TempFile::from_bytes(b"contents").await.unwrap();
+    let path = existing.file_path().clone();
+
+    // Truncating on wrap.
+    let mut options = OpenOptions::new();
+    options.read(true).write(true).truncate(true);
+    let file = TempFile::from_existing_with_options(path.as_path(), Ownership::Borrowed, options)
+        .await
+        .unwrap();
+    assert!(file.is_empty().await.unwrap());
+
+    // Write-only handles are rejected, and owned files are not deleted when rejected.
+    let mut options = OpenOptions::new();
+    options.write(true);
+    let error = TempFile::from_existing_with_options(path.as_path(), Ownership::Owned, options)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&error, Error::Io { source, .. } if source.kind() == std::io::ErrorKind::InvalidInput)
+    );
+    assert!(path.is_file());
+
+    // Missing files are rejected.
+    let mut options = OpenOptions::new();
+    options.read(true).create(true);
+    let missing = path.with_extension("missing");
+    let error = TempFile::from_existing_with_options(missing.as_path(), Ownership::Owned, options)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::InvalidFile));
+}
+
+#[tokio::test]
+async fn set_len_affects_all_references() {
+    use tokio::io::AsyncSeekExt;
+
+    let mut file = TempFile::from_bytes(b"some data").await.unwrap();
+    let other = file.open_ro().await.unwrap();
+
+    file.set_len(4).await.unwrap();
+    assert_eq!(other.len().await.unwrap(), 4);
+
+    file.seek(std::io::SeekFrom::End(0)).await.unwrap();
+    file.truncate().await.unwrap();
+    assert_eq!(file.stream_position().await.unwrap(), 0);
+    assert!(other.is_empty().await.unwrap());
+}
+
+#[tokio::test]
+async fn sync_before_persisting() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let dest = dir.join("synced.bin");
+
+    let mut file = TempFile::new_near(dest.as_path()).await.unwrap();
+    file.write_all_and_flush(b"durable").await.unwrap();
+    file.sync_data().await.unwrap();
+    file.sync_all().await.unwrap();
+    file.persist_to(dest.as_path()).await.unwrap();
+    assert_eq!(tokio::fs::read(&dest).await.unwrap(), b"durable");
+}
+
+#[tokio::test]
+async fn io_errors_name_the_path() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    tokio::fs::create_dir(dir.join("taken")).await.unwrap();
+
+    // The name is taken by a directory, so the file cannot be opened.
+    let error = TempFile::new_with_name_in("taken", dir.dir_path().as_path())
+        .await
+        .unwrap_err();
+    let path = dir.join("taken");
+    assert!(matches!(&error, async_tempfile::Error::Io { path: Some(p), .. } if p == &path));
+    assert!(error.to_string().starts_with(&path.display().to_string()));
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn mode_is_applied_at_creation() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let file = TempFile::new_with_mode_in(0o600, dir.dir_path().as_path())
+        .await
+        .unwrap();
+    let mode = file.metadata().await.unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    file.set_permissions(std::fs::Permissions::from_mode(0o644))
+        .await
+        .unwrap();
+    let mode = tokio::fs::metadata(file.file_path())
+        .await
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o644);
+
+    // Missing directories are reported as such.
+    let missing = dir.join("missing");
+    assert!(matches!(
+        TempFile::new_with_mode_in(0o600, missing.as_path()).await,
+        Err(async_tempfile::Error::DirectoryNotFound(_))
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "rt-multi-thread")]
+async fn flush_on_drop_completes_pending_writes() {
+    use tokio::io::AsyncWriteExt;
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    for _ in 0..16 {
+        let mut file = TempFile::new_in(dir.dir_path().as_path()).await.unwrap();
+        file.set_flush_on_drop(true);
+        let other = file.open_ro().await.unwrap();
+        assert!(other.flush_on_drop());
+
+        // Handles are dropped on a worker thread of the runtime.
+        let path = tokio::spawn(async move {
+            file.write_all(&[7u8; 1 << 20]).await.unwrap();
+            file.persist().unwrap()
+        })
+        .await
+        .unwrap();
+
+        // The data was written by the time the handle was dropped.
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 1 << 20);
+        drop(other);
+    }
+}
+
+#[tokio::test]
+async fn builder_enables_flush_on_drop() {
+    let file = TempFile::builder().build().await.unwrap();
+    assert!(!file.flush_on_drop());
+
+    let file = TempFile::builder()
+        .flush_on_drop(true)
+        .build()
+        .await
+        .unwrap();
+    assert!(file.flush_on_drop());
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn shred_on_drop_overwrites_contents() {
+    use std::io::Read;
+
+    for how in ["drop", "close", "remove"] {
+        let mut file = TempFile::builder()
+            .shred_on_drop(true)
+            .build()
+            .await
+            .unwrap();
+        assert!(file.shred_on_drop());
+        file.write_all_and_flush(b"secret").await.unwrap();
+
+        // The observer keeps the contents accessible after the file was deleted.
+        let mut observer = std::fs::File::open(file.file_path()).unwrap();
+        let path = file.file_path().clone();
+        match how {
+            "close" => file.close().await.unwrap(),
+            "remove" => file.remove().await.unwrap(),
+            _ => drop(file),
+        }
+        assert!(!path.exists());
+
+        let mut content = Vec::new();
+        observer.read_to_end(&mut content).unwrap();
+        assert_eq!(content, [0u8; 6]);
+    }
+}
+
+#[test]
+#[cfg(feature = "blocking")]
+fn blocking_file_is_deleted_on_drop() {
+    use std::io::{Read, Seek, Write};
+
+    let mut file = async_tempfile::blocking::TempFile::new().unwrap();
+    file.write_all(b"data").unwrap();
+    file.rewind().unwrap();
+    let mut content = String::new();
+    file.read_to_string(&mut content).unwrap();
+    assert_eq!(content, "data");
+
+    let path = file.file_path().clone();
+    let borrowed = async_tempfile::blocking::TempFile::from_existing(
+        path.as_path(),
+        async_tempfile::Ownership::Borrowed,
+    )
+    .unwrap();
+    drop(borrowed);
+    assert!(path.exists());
+
+    drop(file);
+    assert!(!path.exists());
+
+    let missing = std::env::temp_dir().join("atmp_missing_dir");
+    assert!(matches!(
+        async_tempfile::blocking::TempFile::new_in(missing.as_path()),
+        Err(async_tempfile::Error::DirectoryNotFound(_))
+    ));
+}
+
+#[test]
+#[cfg(feature = "async-std")]
+fn async_std_file_is_deleted_after_last_reference() {
+    use async_std::prelude::*;
+
+    async_std::task::block_on(async {
+        let dir = async_tempfile::async_std::TempDir::new().await.unwrap();
+        let mut file = async_tempfile::async_std::TempFile::new_in(dir.dir_path().as_path())
+            .await
+            .unwrap();
+        file.write_all(b"data").await.unwrap();
+        file.flush().await.unwrap();
+
+        let mut reader = file.open_ro().await.unwrap();
+        let mut content = String::new();
+        reader.read_to_string(&mut content).await.unwrap();
+        assert_eq!(content, "data");
+
+        let path = file.file_path().clone();
+        file.close().await.unwrap();
+        assert!(path.exists());
+        reader.close().await.unwrap();
+        assert!(!path.exists());
+
+        // Dropping within a task removes the file on the blocking thread pool.
+        let file = async_tempfile::async_std::TempFile::new_in(dir.dir_path().as_path())
+            .await
+            .unwrap();
+        let path = file.file_path().clone();
+        drop(file);
+        for _ in 0..100 {
+            if !path.exists() {
+                break;
+            }
+            async_std::task::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(!path.exists());
+
+        let dir_path = dir.dir_path().clone();
+        dir.close().await.unwrap();
+        assert!(!dir_path.exists());
+    });
+
+    // Outside of a task, dropping removes the directory immediately.
+    let dir = async_std::task::block_on(async_tempfile::async_std::TempDir::new()).unwrap();
+    let dir_path = dir.dir_path().clone();
+    drop(dir);
+    assert!(!dir_path.exists());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn drop_on_multi_thread_runtime_deletes_immediately() {
+    let file = TempFile::new().await.unwrap();
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let file_path = file.file_path().clone();
+    let dir_path = dir.dir_path().clone();
+
+    // Dropping within a task runs on a worker thread of the runtime.
+    tokio::spawn(async move {
+        drop(file);
+        drop(dir);
+    })
+    .await
+    .unwrap();
+
+    assert!(!file_path.exists());
+    assert!(!dir_path.exists());
+}
+
+#[tokio::test]
+async fn advisory_locks_coordinate_handles() {
+    let file = TempFile::new().await.unwrap();
+    let clone = file.try_clone().await.unwrap();
+    let other = file.open_rw().await.unwrap();
+
+    file.lock_shared().await.unwrap();
+    assert!(other.try_lock_shared().await.unwrap());
+    assert!(!clone.try_lock_exclusive().await.unwrap());
+    other.unlock().await.unwrap();
+
+    // Handles sharing the open file share the lock.
+    file.lock_exclusive().await.unwrap();
+    assert!(!other.try_lock_shared().await.unwrap());
+    clone.unlock().await.unwrap();
+    assert!(other.try_lock_exclusive().await.unwrap());
+}
+
+#[tokio::test]
+#[cfg(feature = "mmap")]
+async fn mmap_reflects_file_contents() {
+    let mut file = TempFile::from_bytes(b"hello").await.unwrap();
+
+    // SAFETY: The file is not modified while the mappings are alive.
+    let map = unsafe { file.mmap() }.unwrap();
+    assert_eq!(&map[..], b"hello");
+    drop(map);
+
+    // SAFETY: The file is only modified through the mapping while it is alive.
+    let mut map = unsafe { file.mmap_mut() }.unwrap();
+    map.copy_from_slice(b"world");
+    map.flush().unwrap();
+    drop(map);
+    assert_eq!(file.read_all().await.unwrap(), b"world");
+
+    // Read-only handles cannot be mapped for writing.
+    let reader = file.open_ro().await.unwrap();
+    assert!(unsafe { reader.mmap_mut() }.is_err());
+}
+
+#[tokio::test]
+async fn spooled_file_spills_past_threshold() {
+    use async_tempfile::SpooledTempFile;
+    use std::io::SeekFrom;
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let mut file = SpooledTempFile::new_in(4, dir.as_path());
+    file.write_all(b"abcd").await.unwrap();
+    assert!(!file.is_spilled());
+    assert!(file.as_file().is_none());
+
+    // Seeking back and overwriting within the threshold keeps the data in memory.
+    file.seek(SeekFrom::Start(2)).await.unwrap();
+    file.write_all(b"CD").await.unwrap();
+    assert!(!file.is_spilled());
+
+    // The position is kept when spilling.
+    file.seek(SeekFrom::Start(1)).await.unwrap();
+    file.write_all(b"BCDE").await.unwrap();
+    assert!(file.is_spilled());
+    let path = file.as_file().unwrap().file_path().clone();
+    assert!(path.starts_with(dir.as_path()));
+
+    file.rewind().await.unwrap();
+    let mut content = String::new();
+    file.read_to_string(&mut content).await.unwrap();
+    assert_eq!(content, "aBCDE");
+
+    drop(file);
+    assert!(!path.exists());
+
+    // Spilling explicitly creates the file right away.
+    let mut file = SpooledTempFile::new(1024);
+    file.spill().await.unwrap();
+    assert!(file.is_spilled());
+}
+
+#[tokio::test]
+async fn names_cannot_escape_directory() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let absolute = dir.join("absolute");
+    let names = ["foo/bar", "../x", "..", "", absolute.to_str().unwrap()];
+
+    for name in names {
+        assert!(matches!(
+            TempFile::new_with_name_in(name, dir.as_path()).await,
+            Err(async_tempfile::Error::InvalidFile)
+        ));
+        assert!(matches!(
+            async_tempfile::TempDir::new_with_name_in(name, dir.as_path()).await,
+            Err(async_tempfile::Error::InvalidFile)
+        ));
+    }
+    assert!(matches!(
+        TempFile::new_with_name("../x").await,
+        Err(async_tempfile::Error::InvalidFile)
+    ));
+
+    // Nothing was created inside of the directory.
+    assert_eq!(dir.entries().await.unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn temp_path_keeps_name_reserved_without_handle() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let path = async_tempfile::TempPath::new_in(dir.as_path())
+        .await
+        .unwrap();
+    assert!(path.is_file());
+    assert_eq!(path.parent(), Some(dir.as_path()));
+
+    // Another handle may open the file exclusively, e.g. for writing.
+    tokio::fs::write(path.path(), b"output").await.unwrap();
+    let file_path = path.path().to_path_buf();
+    drop(path);
+    assert!(!file_path.exists());
+
+    let kept = async_tempfile::TempPath::new_in(dir.as_path())
+        .await
+        .unwrap()
+        .persist();
+    assert!(kept.is_file());
+}
+
+#[tokio::test]
+async fn into_file_keeps_file_for_all_references() {
+    use tokio::io::AsyncWriteExt;
+
+    let file = TempFile::new().await.unwrap();
+    let other = file.open_rw().await.unwrap();
+    let path = file.file_path().clone();
+
+    let mut inner = file.into_file().unwrap();
+    assert_eq!(other.ownership(), async_tempfile::Ownership::Borrowed);
+    drop(other);
+    assert!(path.is_file());
+
+    inner.write_all(b"kept").await.unwrap();
+    inner.flush().await.unwrap();
+    drop(inner);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), b"kept");
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+async fn std_file_round_trip() {
+    let file = TempFile::from_bytes(b"contents").await.unwrap();
+    let path = file.file_path().clone();
+
+    let std_file = file.into_std().await.unwrap();
+    assert!(path.is_file());
+
+    let mut file = TempFile::from_std(std_file, path.as_path(), async_tempfile::Ownership::Owned)
+        .await
+        .unwrap();
+    assert_eq!(file.read_all().await.unwrap(), b"contents");
+    drop(file);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn from_std_rejects_write_only_handles() {
+    let file = TempFile::new().await.unwrap();
+    let std_file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(file.file_path())
+        .unwrap();
+
+    let result =
+        TempFile::from_std(std_file, file.as_path(), async_tempfile::Ownership::Owned).await;
+    assert!(result.is_err());
+    assert!(file.file_path().is_file());
+}
+
+#[tokio::test]
+async fn clone_ref_shares_the_file() {
+    let file = TempFile::new().await.unwrap();
+    let path = file.file_path().clone();
+
+    let reference = file.clone_ref().unwrap();
+    assert_eq!(reference, file);
+    assert_eq!(file.reference_count(), 2);
+
+    drop(file);
+    assert!(path.is_file());
+    drop(reference);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn rename_keeps_file_in_its_directory() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let file = TempFile::new_in(dir.as_path()).await.unwrap();
+    let clone = file.open_ro().await.unwrap();
+    let old_path = file.file_path().clone();
+
+    file.rename("logical.bin").await.unwrap();
+    assert_eq!(file.file_path(), &dir.join("logical.bin"));
+    assert_eq!(clone.file_path(), file.file_path());
+    assert!(!old_path.exists());
+
+    let invalid = file.rename("../escaped.bin").await;
+    assert!(matches!(invalid, Err(async_tempfile::Error::InvalidFile)));
+
+    let path = file.file_path().clone();
+    drop(file);
+    drop(clone);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn copy_to_leaves_file_untouched() {
+    use tokio::io::AsyncSeekExt;
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let mut file = TempFile::new().await.unwrap();
+    file.write_all_and_flush(b"contents").await.unwrap();
+    file.seek(std::io::SeekFrom::Start(3)).await.unwrap();
+
+    let dest = dir.join("copy.bin");
+    assert_eq!(file.copy_to(&dest).await.unwrap(), 8);
+    assert_eq!(tokio::fs::read(&dest).await.unwrap(), b"contents");
+    assert_eq!(file.stream_position().await.unwrap(), 3);
+    assert_eq!(file.ownership(), async_tempfile::Ownership::Owned);
+
+    let path = file.file_path().clone();
+    drop(file);
+    assert!(!path.exists());
+    assert!(dest.is_file());
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn serializes_path() {
+    let file = TempFile::new().await.unwrap();
+    let json = serde_json::to_value(&file).unwrap();
+    assert_eq!(
+        json.as_str().map(std::path::Path::new),
+        Some(file.as_path())
+    );
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let json = serde_json::to_value(&dir).unwrap();
+    assert_eq!(json.as_str().map(std::path::Path::new), Some(dir.as_path()));
+}
+
+// The path is only mutated by renaming, which this test does not do.
+#[allow(clippy::mutable_key_type)]
+#[tokio::test]
+async fn equality_follows_the_file() {
+    let file = TempFile::new().await.unwrap();
+    let other = TempFile::new().await.unwrap();
+
+    assert_eq!(file.try_clone().await.unwrap(), file);
+    assert_eq!(file.open_ro().await.unwrap(), file);
+    assert_ne!(other, file);
+
+    let set = std::collections::HashSet::from([
+        file.try_clone().await.unwrap(),
+        file.open_rw().await.unwrap(),
+        other,
+    ]);
+    assert_eq!(set.len(), 2);
+}
+
+#[tokio::test]
+async fn append_handles_do_not_overwrite_each_other() {
+    let mut file = TempFile::new().await.unwrap();
+
+    let mut tasks = Vec::new();
+    for writer in 0..2 {
+        let mut log = file.open_append().await.unwrap();
+        tasks.push(tokio::spawn(async move {
+            for line in 0..50 {
+                let record = format!("{writer}:{line:02}\n");
+                log.write_all_and_flush(record.as_bytes()).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        }));
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let content = String::from_utf8(file.read_all().await.unwrap()).unwrap();
+    let mut lines: Vec<_> = content.lines().collect();
+    assert_eq!(lines.len(), 100);
+    lines.sort_unstable();
+    lines.dedup();
+    assert_eq!(lines.len(), 100);
+}
+
+#[tokio::test]
+async fn buffered_wrappers_keep_file_alive() {
+    use tokio::io::{AsyncBufReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let file = TempFile::new().await.unwrap();
+    let path = file.file_path().clone();
+
+    let mut writer = file.buf_writer();
+    writer.write_all(b"one\ntwo\n").await.unwrap();
+    writer.flush().await.unwrap();
+    assert!(path.is_file());
+
+    let mut file = writer.into_inner();
+    file.rewind().await.unwrap();
+    let mut lines = file.buf_reader().lines();
+    assert_eq!(lines.next_line().await.unwrap().as_deref(), Some("one"));
+    assert_eq!(lines.next_line().await.unwrap().as_deref(), Some("two"));
+
+    drop(lines);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn exclusive_creation_refuses_existing_files() {
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let file = TempFile::new_with_name_in_exclusive("unique.bin", dir.as_path())
+        .await
+        .unwrap();
+
+    let result = TempFile::new_with_name_in_exclusive("unique.bin", dir.as_path()).await;
+    assert_eq!(
+        result.unwrap_err().kind(),
+        async_tempfile::ErrorKind::AlreadyExists
+    );
+
+    // The existing file is neither owned nor deleted by the failed attempt.
+    assert!(file.file_path().is_file());
+}
+
+#[tokio::test]
+async fn random_names_never_reuse_existing_files() {
+    use async_tempfile::NameGenerator;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns the name of an existing file for the first `taken` calls, and fresh names
+    /// afterwards.
+    struct Colliding {
+        calls: AtomicUsize,
+        taken: usize,
+    }
+
+    impl NameGenerator for Colliding {
+        fn generate(&self, prefix: &str) -> String {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            format!("{prefix}{}", if call < self.taken { 0 } else { call })
+        }
+    }
+
+    let dir = async_tempfile::TempDir::new().await.unwrap();
+    let existing = dir.join("atmp_0");
+    tokio::fs::write(&existing, b"data").await.unwrap();
+
+    let generator = Colliding {
+        calls: AtomicUsize::new(0),
+        taken: 1,
+    };
+    let file = TempFile::new_with_generator(&generator, dir.as_path())
+        .await
+        .unwrap();
+    assert_eq!(generator.calls.load(Ordering::SeqCst), 2);
+    assert_eq!(file.file_path(), &dir.join("atmp_1"));
+    assert_eq!(tokio::fs::read(&existing).await.unwrap(), b"data");
+
+    // The retry gives up once all attempts collided.
+    let generator = Colliding {
+        calls: AtomicUsize::new(0),
+        taken: usize::MAX,
+    };
+    let error = TempFile::new_with_generator(&generator, dir.as_path())
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), async_tempfile::ErrorKind::AlreadyExists);
+    assert_eq!(generator.calls.load(Ordering::SeqCst), 16);
+    assert_eq!(tokio::fs::read(&existing).await.unwrap(), b"data");
+
+    // A single attempt is made even when no attempts are requested.
+    let file = TempFile::builder()
+        .dir(dir.as_path())
+        .max_attempts(0)
+        .build()
+        .await
+        .unwrap();
+    assert!(file.file_path().is_file());
+}
+
+#[tokio::test]
+async fn generated_dir_names_never_reuse_existing_dirs() {
+    use async_tempfile::NameGenerator;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns the name of an existing directory for the first `taken` calls, and fresh names
+    /// afterwards.
+    struct Colliding {
+        calls: AtomicUsize,
+        taken: usize,
+    }
+
+    impl NameGenerator for Colliding {
+        fn generate(&self, prefix: &str) -> String {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            format!("{prefix}{}", if call < self.taken { 0 } else { call })
+        }
+    }
+
+    let root = async_tempfile::TempDir::new().await.unwrap();
+    let existing = root.join("atmpd_0");
+    tokio::fs::create_dir(&existing).await.unwrap();
+    tokio::fs::write(existing.join("data.bin"), b"data")
+        .await
+        .unwrap();
+
+    let generator = Colliding {
+        calls: AtomicUsize::new(0),
+        taken: 1,
+    };
+    let dir = async_tempfile::TempDir::new_with_generator(&generator, root.as_path())
+        .await
+        .unwrap();
+    assert_eq!(generator.calls.load(Ordering::SeqCst), 2);
+    assert_eq!(dir.dir_path(), &root.join("atmpd_1"));
+
+    // The retry gives up once all attempts collided, leaving the existing directory untouched.
+    let generator = Colliding {
+        calls: AtomicUsize::new(0),
+        taken: usize::MAX,
+    };
+    let error = async_tempfile::TempDir::new_with_generator(&generator, root.as_path())
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), async_tempfile::ErrorKind::AlreadyExists);
+    assert_eq!(generator.calls.load(Ordering::SeqCst), 16);
+    assert!(existing.join("data.bin").is_file());
+}
+
+#[tokio::test]
+async fn exclusive_dir_creation_refuses_existing_dirs() {
+    let root = async_tempfile::TempDir::new().await.unwrap();
+    let existing = root.join("existing");
+    tokio::fs::create_dir(&existing).await.unwrap();
+    tokio::fs::write(existing.join("data.bin"), b"data")
+        .await
+        .unwrap();
+
+    let result =
+        async_tempfile::TempDir::new_with_name_in_exclusive("existing", root.as_path()).await;
+    assert_eq!(
+        result.unwrap_err().kind(),
+        async_tempfile::ErrorKind::AlreadyExists
+    );
+
+    // The existing directory is neither owned nor deleted by the failed attempt.
+    assert!(existing.join("data.bin").is_file());
+
+    let dir = async_tempfile::TempDir::new_with_name_in_exclusive("fresh", root.as_path())
+        .await
+        .unwrap();
+    let dir_path = dir.dir_path().clone();
+    assert!(dir_path.is_dir());
+    drop(dir);
+    assert!(!dir_path.exists());
+}
+
+#[tokio::test]
+async fn detach_keeps_file() {
+    let file = TempFile::new().await.unwrap();
+    let other = file.open_ro().await.unwrap();
+
+    let path = file.detach();
+    assert_eq!(other.ownership(), async_tempfile::Ownership::Borrowed);
+    drop(other);
+    assert!(path.is_file());
+    tokio::fs::remove_file(path).await.unwrap();
+}
+
+#[tokio::test]
+async fn reopen_has_an_independent_cursor() {
+    use tokio::io::AsyncSeekExt;
+
+    let mut file = TempFile::new().await.unwrap();
+    file.write_all_and_flush(b"data").await.unwrap();
+
+    let mut reopened = file.reopen().await.unwrap();
+    assert_eq!(reopened.stream_position().await.unwrap(), 0);
+    assert_eq!(file.stream_position().await.unwrap(), 4);
+    assert_eq!(reopened.file_path(), file.file_path());
+}
+
+#[tokio::test]
+async fn clone_independent_cursor_does_not_share_the_cursor() {
+    use tokio::io::AsyncSeekExt;
+
+    let mut file = TempFile::new().await.unwrap();
+    let mut shared = file.try_clone().await.unwrap();
+    let mut independent = file.clone_independent_cursor().await.unwrap();
+    file.write_all_and_flush(b"data").await.unwrap();
+
+    assert_eq!(shared.stream_position().await.unwrap(), 4);
+    assert_eq!(independent.stream_position().await.unwrap(), 0);
+}
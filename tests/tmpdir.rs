@@ -0,0 +1,88 @@
+//! Tests for the resolution of the default location. These live in their own test binary,
+//! since changing `TMPDIR` or the configured default directory affects all files created
+//! in the default location.
+
+#![cfg(unix)]
+
+use async_tempfile::{TempDir, TempFile};
+use tokio::sync::{Mutex, MutexGuard};
+
+/// Serializes the tests, as they change the default location of the process.
+static DEFAULT_LOCATION: Mutex<()> = Mutex::const_new(());
+
+/// Takes exclusive control of the default location, ignoring the runtime directory of the user.
+async fn lock_default_location() -> MutexGuard<'static, ()> {
+    let guard = DEFAULT_LOCATION.lock().await;
+    #[cfg(all(target_os = "linux", feature = "xdg-runtime-dir"))]
+    std::env::remove_var("XDG_RUNTIME_DIR");
+    guard
+}
+
+#[tokio::test]
+async fn default_dir_follows_tmpdir_changes() {
+    let _guard = lock_default_location().await;
+    let original = std::env::var_os("TMPDIR");
+    let first = TempDir::new_in(std::env::temp_dir()).await.unwrap();
+    let second = TempDir::new_in(std::env::temp_dir()).await.unwrap();
+
+    std::env::set_var("TMPDIR", first.as_path());
+    let file = TempFile::new().await.unwrap();
+    assert_eq!(file.file_path().parent(), Some(first.as_path()));
+
+    std::env::set_var("TMPDIR", second.as_path());
+    let file = TempFile::new().await.unwrap();
+    assert_eq!(file.file_path().parent(), Some(second.as_path()));
+    let dir = TempDir::new().await.unwrap();
+    assert_eq!(dir.parent(), Some(second.as_path()));
+
+    // Explicitly specified directories take precedence.
+    let explicit = TempFile::new_in(first.as_path()).await.unwrap();
+    assert_eq!(explicit.file_path().parent(), Some(first.as_path()));
+
+    match original {
+        Some(original) => std::env::set_var("TMPDIR", original),
+        None => std::env::remove_var("TMPDIR"),
+    }
+}
+
+#[tokio::test]
+async fn configured_default_dir_takes_precedence() {
+    let _guard = lock_default_location().await;
+    let configured = TempDir::new_in(std::env::temp_dir()).await.unwrap();
+
+    async_tempfile::set_default_dir(Some(configured.dir_path().clone()));
+    assert_eq!(async_tempfile::default_dir(), configured.dir_path().clone());
+    let file = TempFile::new().await.unwrap();
+    assert_eq!(file.file_path().parent(), Some(configured.as_path()));
+    let dir = TempDir::new().await.unwrap();
+    assert_eq!(dir.parent(), Some(configured.as_path()));
+
+    // Restoring the default applies to items created afterwards only.
+    async_tempfile::set_default_dir(None);
+    assert_eq!(async_tempfile::default_dir(), std::env::temp_dir());
+    let other = TempFile::new().await.unwrap();
+    assert_eq!(
+        other.file_path().parent(),
+        Some(std::env::temp_dir().as_path())
+    );
+    assert!(file.file_path().starts_with(configured.as_path()));
+}
+
+#[cfg(all(target_os = "linux", feature = "xdg-runtime-dir"))]
+#[tokio::test]
+async fn default_dir_prefers_runtime_dir() {
+    let _guard = lock_default_location().await;
+    let runtime = TempDir::new_in(std::env::temp_dir()).await.unwrap();
+
+    std::env::set_var("XDG_RUNTIME_DIR", runtime.as_path());
+    let file = TempFile::new().await.unwrap();
+    assert_eq!(file.file_path().parent(), Some(runtime.as_path()));
+
+    // Relative and missing directories are ignored.
+    std::env::set_var("XDG_RUNTIME_DIR", "relative");
+    assert_eq!(async_tempfile::default_dir(), std::env::temp_dir());
+    std::env::set_var("XDG_RUNTIME_DIR", runtime.join("missing"));
+    assert_eq!(async_tempfile::default_dir(), std::env::temp_dir());
+
+    std::env::remove_var("XDG_RUNTIME_DIR");
+}